@@ -0,0 +1,58 @@
+use std::collections::BTreeMap;
+
+use public_api::PublicItem;
+
+/// The public API of a crate built in several different configurations, e.g.
+/// with different sets of features enabled. Keeps track of what
+/// configurations each public item is present in.
+#[derive(Debug, Default)]
+pub struct ApiMatrix {
+    /// Every item that is present in at least one configuration, mapped to the
+    /// names of the configurations it is present in.
+    items: BTreeMap<PublicItem, Vec<String>>,
+}
+
+impl ApiMatrix {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the public items of a crate built in the configuration named
+    /// `config`.
+    pub fn add(&mut self, config: impl Into<String>, items: Vec<PublicItem>) {
+        let config = config.into();
+        for item in items {
+            let configs = self.items.entry(item).or_default();
+            if !configs.contains(&config) {
+                configs.push(config.clone());
+            }
+        }
+    }
+
+    /// All items that are present in at least one configuration, sorted,
+    /// together with the configurations each item is present in.
+    pub fn items(&self) -> impl Iterator<Item = (&PublicItem, &[String])> {
+        self.items
+            .iter()
+            .map(|(item, configs)| (item, configs.as_slice()))
+    }
+
+    /// The configurations `item` is present in. Empty if the item is not
+    /// present in any configuration.
+    pub fn configs_of(&self, item: &PublicItem) -> &[String] {
+        self.items.get(item).map_or(&[], Vec::as_slice)
+    }
+
+    /// The items present in the configuration named `config`, sorted.
+    pub fn items_of(&self, config: &str) -> Vec<PublicItem> {
+        self.items()
+            .filter(|(_, configs)| configs.iter().any(|c| c == config))
+            .map(|(item, _)| item.clone())
+            .collect()
+    }
+
+    /// Whether or not `item` is present in the configuration named `config`.
+    pub fn contains(&self, item: &PublicItem, config: &str) -> bool {
+        self.configs_of(item).iter().any(|c| c == config)
+    }
+}
@@ -19,6 +19,16 @@ pub enum DenyMethod {
 }
 
 impl DenyMethod {
+    /// The name of the method as given on the command line.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            DenyMethod::All => "all",
+            DenyMethod::Added => "added",
+            DenyMethod::Changed => "changed",
+            DenyMethod::Removed => "removed",
+        }
+    }
+
     pub(crate) fn deny_added(self) -> bool {
         std::matches!(self, DenyMethod::All | DenyMethod::Added)
     }
@@ -32,6 +42,48 @@ impl DenyMethod {
     }
 }
 
+#[derive(Copy, Clone, Debug, Eq, PartialEq, clap::ArgEnum)]
+#[clap(rename_all = "lower")]
+pub enum OutputFormat {
+    /// Human readable text
+    Plain,
+
+    /// GitHub Actions workflow commands that annotate the source code of
+    /// changed items
+    Github,
+
+    /// A SARIF 2.1.0 log of changed items
+    Sarif,
+
+    /// A `JUnit` XML report with a testcase for each changed item
+    Junit,
+
+    /// A self-contained HTML page
+    Html,
+}
+
+impl OutputFormat {
+    /// The name of the format as given on the command line.
+    pub(crate) fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Plain => "plain",
+            OutputFormat::Github => "github",
+            OutputFormat::Sarif => "sarif",
+            OutputFormat::Junit => "junit",
+            OutputFormat::Html => "html",
+        }
+    }
+
+    /// Whether the format can only be used when diffing, since it is about
+    /// changes.
+    pub(crate) fn only_for_diffs(self) -> bool {
+        std::matches!(
+            self,
+            OutputFormat::Github | OutputFormat::Sarif | OutputFormat::Junit
+        )
+    }
+}
+
 #[derive(Debug)]
 pub enum Color {
     Auto,
@@ -53,9 +105,15 @@ impl FromStr for Color {
 }
 
 impl Color {
+    /// Whether to use colors when printing to stdout.
     pub fn active(&self) -> bool {
+        self.active_for(atty::Stream::Stdout)
+    }
+
+    /// Whether to use colors when printing to `stream`.
+    pub fn active_for(&self, stream: atty::Stream) -> bool {
         match self {
-            Color::Auto => atty::is(atty::Stream::Stdout), // We should not assume Stdout here, but good enough for now
+            Color::Auto => atty::is(stream),
             Color::Never => false,
             Color::Always => true,
         }
@@ -4,6 +4,9 @@ use public_api::{diff::ChangedPublicItem, PublicItem};
 pub enum Error {
     #[error("The API diff is not allowed as per --deny: {0}")]
     DiffDenied(Violations),
+
+    #[error("Types from these crates are part of the public API, which is not allowed as per --deny-leaked-types: {}", .0.join(", "))]
+    LeakedTypesDenied(Vec<String>),
 }
 
 #[derive(Debug)]
@@ -42,22 +45,37 @@ impl Violations {
     pub fn extend_removed<I: Iterator<Item = PublicItem>>(&mut self, removed: I) {
         self.removed.extend(removed);
     }
+
+    pub fn added(&self) -> &[PublicItem] {
+        &self.added
+    }
+
+    pub fn changed(&self) -> &[ChangedPublicItem] {
+        &self.changed
+    }
+
+    pub fn removed(&self) -> &[PublicItem] {
+        &self.removed
+    }
 }
 
 impl std::fmt::Display for Violations {
+    /// Counts the violations, e.g. `1 removed item, 2 changed items`. The items
+    /// themselves are listed by [`crate::plain::Plain::print_violations`].
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if !self.added.is_empty() {
-            write!(f, "Added items not allowed: {:?} ", self.added)?;
-        }
-
-        if !self.changed.is_empty() {
-            write!(f, "Changed items not allowed: {:?} ", self.changed)?;
-        }
-
-        if !self.removed.is_empty() {
-            write!(f, "Removed items not allowed: {:?} ", self.removed)?;
-        }
+        let counts: Vec<String> = [
+            (self.removed.len(), "removed"),
+            (self.changed.len(), "changed"),
+            (self.added.len(), "added"),
+        ]
+        .into_iter()
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, kind)| {
+            let plural = if count == 1 { "" } else { "s" };
+            format!("{count} {kind} item{plural}")
+        })
+        .collect();
 
-        Ok(())
+        write!(f, "{}", counts.join(", "))
     }
 }
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use public_api::{diff::PublicItemsDiff, PublicItem};
+use rustdoc_json::{BuildError, BuildOptions};
+
+use crate::api_matrix::ApiMatrix;
+use crate::{build_options, collect_public_api, virtual_manifest_error, Args};
+
+/// Name of the configuration built with `--no-default-features`.
+const NO_DEFAULT_FEATURES: &str = "no-default-features";
+
+/// Name of the configuration built without any feature flags. This is also the
+/// name of the feature that lists the default features.
+const DEFAULT: &str = "default";
+
+/// Name of the configuration built with `--all-features`.
+const ALL_FEATURES: &str = "all-features";
+
+/// Builds rustdoc JSON for all feature configurations of interest and collects
+/// the public API of each. The configurations are: no features, default
+/// features, each feature individually, and all features.
+pub fn collect(args: &Args) -> Result<ApiMatrix> {
+    let mut matrix = ApiMatrix::new();
+    for (config, build_options) in configurations(args)? {
+        matrix.add(config, collect_public_api(args, build_options)?.items);
+    }
+    Ok(matrix)
+}
+
+fn configurations(args: &Args) -> Result<Vec<(String, BuildOptions)>> {
+    let mut configurations = vec![
+        (
+            NO_DEFAULT_FEATURES.to_owned(),
+            build_options(args).no_default_features(true),
+        ),
+        (DEFAULT.to_owned(), build_options(args)),
+    ];
+    for feature in package_features(args)? {
+        let build_options = build_options(args)
+            .no_default_features(true)
+            .features([&feature]);
+        configurations.push((feature, build_options));
+    }
+    configurations.push((
+        ALL_FEATURES.to_owned(),
+        build_options(args).all_features(true),
+    ));
+    Ok(configurations)
+}
+
+/// Returns the sorted names of all features of the package, except `default`.
+fn package_features(args: &Args) -> Result<Vec<String>> {
+    let features = match rustdoc_json::features(&args.manifest_path, args.package.as_deref()) {
+        Err(BuildError::VirtualManifest(manifest_path)) => {
+            return virtual_manifest_error(&manifest_path)
+        }
+        res => res?,
+    };
+
+    Ok(features
+        .into_iter()
+        .filter(|feature| feature != DEFAULT)
+        .collect())
+}
+
+/// Returns the features required for `item` to be part of the public API, or
+/// `None` if it is part of the public API even with `--no-default-features`.
+///
+/// The features are the ones that enable the item when enabled on their own,
+/// with `default` meaning the default features. If the item only shows up when
+/// a combination of features is enabled, `all-features` is returned.
+pub fn required_features<'a>(matrix: &'a ApiMatrix, item: &PublicItem) -> Option<Vec<&'a str>> {
+    let configs = matrix.configs_of(item);
+    if configs.iter().any(|config| config == NO_DEFAULT_FEATURES) {
+        return None;
+    }
+
+    let features = configs
+        .iter()
+        .map(String::as_str)
+        .filter(|config| *config != ALL_FEATURES)
+        .collect::<Vec<_>>();
+    if features.is_empty() {
+        Some(vec![ALL_FEATURES])
+    } else {
+        Some(features)
+    }
+}
+
+/// Describes what features `item` requires, e.g. `features: default, foo`.
+/// Returns `None` if the item does not require any features.
+pub fn annotation(matrix: &ApiMatrix, item: &PublicItem) -> Option<String> {
+    required_features(matrix, item).map(|features| format!("features: {}", features.join(", ")))
+}
+
+/// The difference between the public APIs of two [`ApiMatrix`]es built by
+/// [`collect()`].
+pub struct FeatureMatrixDiff<'a> {
+    /// The diff of all items, regardless of what features they require.
+    pub diff: PublicItemsDiff,
+
+    /// Items that used to be part of the public API even with
+    /// `--no-default-features`, but that now require features. This breaks
+    /// downstream users that depend on the crate with `default-features =
+    /// false`, even if the items remain enabled by default.
+    pub now_require_features: Vec<&'a PublicItem>,
+
+    /// Items that used to be enabled by default, but that now require
+    /// non-default features.
+    pub no_longer_default: Vec<&'a PublicItem>,
+}
+
+impl<'a> FeatureMatrixDiff<'a> {
+    pub fn between(old: &ApiMatrix, new: &'a ApiMatrix) -> Self {
+        let all_items = |matrix: &ApiMatrix| {
+            matrix
+                .items()
+                .map(|(item, _)| item.clone())
+                .collect::<Vec<_>>()
+        };
+        let diff = PublicItemsDiff::between(all_items(old), all_items(new));
+
+        let mut now_require_features = vec![];
+        let mut no_longer_default = vec![];
+        for (item, _) in new.items() {
+            if old.configs_of(item).is_empty() {
+                continue; // Added items are part of `diff`
+            }
+
+            if required_features(old, item).is_none() && required_features(new, item).is_some() {
+                now_require_features.push(item);
+            } else if old.contains(item, DEFAULT) && !new.contains(item, DEFAULT) {
+                no_longer_default.push(item);
+            }
+        }
+
+        Self {
+            diff,
+            now_require_features,
+            no_longer_default,
+        }
+    }
+}
@@ -8,7 +8,7 @@ use anyhow::{anyhow, Context, Result};
 /// Synchronously do a `git checkout` of `commit`.
 /// Returns the name of the original branch/commit.
 pub(crate) fn git_checkout(commit: &str, git_root: &Path, quiet: bool) -> Result<String> {
-    let original_branch = current_branch_or_commit(&git_root)?;
+    let original_branch = current_branch_or_commit(git_root)?;
 
     let mut command = Command::new("git");
     command.current_dir(git_root);
@@ -20,8 +20,7 @@ pub(crate) fn git_checkout(commit: &str, git_root: &Path, quiet: bool) -> Result
         Ok(original_branch)
     } else {
         Err(anyhow!(
-            "Failed to `git checkout {}`, see error message on stdout/stderr.",
-            commit,
+            "Failed to `git checkout {commit}`, see error message on stdout/stderr.",
         ))
     }
 }
@@ -29,7 +28,12 @@ pub(crate) fn git_checkout(commit: &str, git_root: &Path, quiet: bool) -> Result
 /// Goes up the chain of parents and looks for a `.git` dir.
 #[allow(unused)] // It IS used!
 pub(crate) fn git_root_from_manifest_path(manifest_path: &Path) -> Result<PathBuf> {
-    let err_fn = || anyhow!("No `.git` dir when starting from `{:?}`.", &manifest_path);
+    let err_fn = || {
+        anyhow!(
+            "No `.git` dir when starting from `{}`.",
+            manifest_path.display()
+        )
+    };
     let start = std::fs::canonicalize(manifest_path).with_context(err_fn)?;
     let mut candidate_opt = start.parent();
     while let Some(candidate) = candidate_opt {
@@ -78,6 +82,6 @@ fn trimmed_stdout(mut cmd: Command) -> Result<String> {
     if output.status.success() {
         Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
     } else {
-        Err(anyhow!("Failure: {:?}", output))
+        Err(anyhow!("Failure: {output:?}"))
     }
 }
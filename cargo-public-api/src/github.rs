@@ -0,0 +1,127 @@
+use std::io::{Result, Write};
+use std::path::Path;
+
+use public_api::{diff::PublicItemsDiff, PublicItem};
+
+use crate::arg_types::DenyMethod;
+use crate::Args;
+
+/// Prints [GitHub Actions workflow
+/// commands](https://docs.github.com/en/actions/using-workflows/workflow-commands-for-github-actions)
+/// so that API changes show up as annotations on the source code.
+pub struct Github;
+
+impl Github {
+    /// Prints an `::error` for each change denied by `--deny`, and a
+    /// `::notice` for each other change. Only denied changes are called
+    /// breaking changes. Removed items point to the old code, other items to
+    /// the new code.
+    pub fn print_diff(w: &mut dyn Write, args: &Args, diff: &PublicItemsDiff) -> Result<()> {
+        let package_dir = args.package_dir_in_git_root().unwrap_or_default();
+
+        for item in &diff.removed {
+            print_annotation(
+                w,
+                args.denies(DenyMethod::deny_removed),
+                &package_dir,
+                item,
+                &format!("removed {item}"),
+            )?;
+        }
+
+        for changed_item in &diff.changed {
+            print_annotation(
+                w,
+                args.denies(DenyMethod::deny_changed),
+                &package_dir,
+                &changed_item.new,
+                &format!("changed\n-{}\n+{}", changed_item.old, changed_item.new),
+            )?;
+        }
+
+        for item in &diff.added {
+            print_annotation(
+                w,
+                args.denies(DenyMethod::deny_added),
+                &package_dir,
+                item,
+                &format!("added {item}"),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Prints e.g. `::error file=src/lib.rs,line=3,endLine=5::Breaking change:
+/// removed ...` for denied changes, and e.g. `::notice ...::Removed ...` for
+/// other changes. Items without a span are annotated without a location,
+/// which makes the annotation show up in the summary of the workflow run
+/// instead.
+fn print_annotation(
+    w: &mut dyn Write,
+    denied: bool,
+    package_dir: &Path,
+    item: &PublicItem,
+    change: &str,
+) -> Result<()> {
+    let (level, message) = if denied {
+        ("error", format!("Breaking change: {change}"))
+    } else {
+        ("notice", capitalize(change))
+    };
+
+    let mut properties = String::new();
+    if let Some(span) = item.span() {
+        let file = package_dir.join(span.filename());
+        properties = format!(
+            " file={},line={},endLine={}",
+            escape_property(&file.to_string_lossy()),
+            span.begin().0,
+            span.end().0,
+        );
+    }
+
+    writeln!(w, "::{level}{properties}::{}", escape_data(&message))
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_uppercase().chain(chars).collect()
+    })
+}
+
+/// Escapes the message of a workflow command so that e.g. newlines in it do not
+/// end the command.
+fn escape_data(data: &str) -> String {
+    data.replace('%', "%25")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+/// Escapes the value of a property of a workflow command, which in addition to
+/// the message of a workflow command can not contain `:` or `,`.
+fn escape_property(property: &str) -> String {
+    escape_data(property)
+        .replace(':', "%3A")
+        .replace(',', "%2C")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{escape_data, escape_property};
+
+    #[test]
+    fn test_escape_data() {
+        assert_eq!(
+            escape_data("100% changed:\n-a, b\r\n+c"),
+            "100%25 changed:%0A-a, b%0D%0A+c"
+        );
+    }
+
+    #[test]
+    fn test_escape_property() {
+        assert_eq!(escape_property("src/a:b,c%.rs"), "src/a%3Ab%2Cc%25.rs");
+    }
+}
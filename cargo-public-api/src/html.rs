@@ -0,0 +1,227 @@
+use std::io::{Result, Write};
+
+use nu_ansi_term::Color;
+use public_api::{
+    diff::{ChangedPublicItem, PublicItemsDiff},
+    tokens::Token,
+    PublicItem,
+};
+
+use crate::plain::token_color;
+use crate::tree::{Entry, Node};
+
+/// Prints a self-contained HTML page for people who do not live in the
+/// terminal. Items are grouped into a collapsible tree by their paths, and can
+/// be searched for.
+pub struct Html;
+
+/// Styles the syntax highlighting like the terminal theme of
+/// [`crate::plain::token_color`], and changed tokens like
+/// `color_item_with_diff` in [`crate::plain`].
+const STYLE: &str = r"
+body { background: #1e1e1e; color: #d4d4d4; font-family: sans-serif; margin: 2em; }
+code { font-family: monospace; white-space: pre-wrap; }
+input { font-size: 1em; margin: 1em 0; padding: 0.3em; width: 30em; }
+details { margin-left: 1.5em; }
+summary { cursor: pointer; padding: 0.1em 0; }
+.count { color: #808080; }
+ul { list-style: none; margin: 0.2em 0 0.2em 1.5em; padding: 0; }
+li { padding: 0.1em 0; }
+table { border-collapse: collapse; }
+td { border: 1px solid #444; padding: 0.2em 0.5em; vertical-align: top; }
+.blue { color: #569cd6; }
+.cyan { color: #9cdcfe; }
+.yellow { color: #dcdcaa; }
+.green { color: #4ec9b0; }
+.removed-token { color: #ff0000; background: #5f0000; font-weight: bold; }
+.added-token { color: #00ff00; background: #005f00; font-weight: bold; }
+.removed > .marker { color: #f14c4c; }
+.changed > .marker { color: #e5e510; }
+.added > .marker { color: #23d18b; }
+.hidden { display: none; }
+";
+
+/// Hides items that do not contain the text in the search box, and subtrees
+/// without any items left.
+const SCRIPT: &str = r#"
+document.getElementById("search").addEventListener("input", (event) => {
+    const query = event.target.value.toLowerCase();
+    for (const item of document.querySelectorAll("li")) {
+        item.classList.toggle("hidden", !item.textContent.toLowerCase().includes(query));
+    }
+    for (const details of Array.from(document.querySelectorAll("details")).reverse()) {
+        const anyVisible = details.querySelector("li:not(.hidden)") !== null;
+        details.classList.toggle("hidden", !anyVisible);
+        if (query !== "") {
+            details.open = true;
+        }
+    }
+});
+"#;
+
+impl Html {
+    pub fn print_items(w: &mut dyn Write, crate_name: &str, items: &[PublicItem]) -> Result<()> {
+        let tree = Node::from_items(items);
+        let summary = format!("{} items", items.len());
+        print_page(w, &format!("Public API of {crate_name}"), &summary, &tree)
+    }
+
+    pub fn print_diff(w: &mut dyn Write, crate_name: &str, diff: &PublicItemsDiff) -> Result<()> {
+        let tree = Node::from_diff(diff);
+        let summary = format!(
+            "{} removed, {} changed, {} added items",
+            diff.removed.len(),
+            diff.changed.len(),
+            diff.added.len()
+        );
+        print_page(
+            w,
+            &format!("Public API diff of {crate_name}"),
+            &summary,
+            &tree,
+        )
+    }
+}
+
+fn print_page(w: &mut dyn Write, title: &str, summary: &str, tree: &Node<Entry>) -> Result<()> {
+    let title = escape(title);
+    writeln!(w, "<!DOCTYPE html>")?;
+    writeln!(w, r#"<html lang="en">"#)?;
+    writeln!(w, "<head>")?;
+    writeln!(w, r#"<meta charset="utf-8">"#)?;
+    writeln!(w, "<title>{title}</title>")?;
+    writeln!(w, "<style>{STYLE}</style>")?;
+    writeln!(w, "</head>")?;
+    writeln!(w, "<body>")?;
+    writeln!(w, "<h1>{title}</h1>")?;
+    writeln!(w, "<p>{}</p>", escape(summary))?;
+    writeln!(
+        w,
+        r#"<input id="search" type="search" placeholder="Search items" autofocus>"#
+    )?;
+    for node in tree.children.values() {
+        print_node(w, node)?;
+    }
+    writeln!(w, "<script>{SCRIPT}</script>")?;
+    writeln!(w, "</body>")?;
+    writeln!(w, "</html>")
+}
+
+/// Prints a node as a collapsible `<details>`. Its own entries and the entries
+/// of its children without children of their own are listed first, followed
+/// by its other children.
+fn print_node(w: &mut dyn Write, node: &Node<Entry>) -> Result<()> {
+    writeln!(w, "<details open>")?;
+    writeln!(
+        w,
+        r#"<summary><code>{}</code> <span class="count">({})</span></summary>"#,
+        escape(&node.path.join("::")),
+        node.len()
+    )?;
+
+    let (leaves, subtrees): (Vec<_>, Vec<_>) = node
+        .children
+        .values()
+        .partition(|child| child.children.is_empty());
+    let entries: Vec<&Entry> = node
+        .entries
+        .iter()
+        .chain(leaves.iter().flat_map(|leaf| &leaf.entries))
+        .collect();
+    if !entries.is_empty() {
+        writeln!(w, "<ul>")?;
+        for entry in entries {
+            print_entry(w, entry)?;
+        }
+        writeln!(w, "</ul>")?;
+    }
+
+    for subtree in subtrees {
+        print_node(w, subtree)?;
+    }
+    writeln!(w, "</details>")
+}
+
+fn print_entry(w: &mut dyn Write, entry: &Entry) -> Result<()> {
+    match entry {
+        Entry::Item(item) => print_item(w, None, item),
+        Entry::Removed(item) => print_item(w, Some(("removed", "-")), item),
+        Entry::Changed(changed_item) => print_changed_item(w, changed_item),
+        Entry::Added(item) => print_item(w, Some(("added", "+")), item),
+    }
+}
+
+/// Prints `item`, with e.g. a `("removed", "-")` class and marker if it is
+/// part of a diff.
+fn print_item(
+    w: &mut dyn Write,
+    class_and_marker: Option<(&str, &str)>,
+    item: &PublicItem,
+) -> Result<()> {
+    let tokens: String = item.tokens().map(|token| highlight(token, None)).collect();
+    match class_and_marker {
+        Some((class, marker)) => writeln!(
+            w,
+            r#"<li class="{class}"{}><span class="marker">{marker}</span><code>{tokens}</code></li>"#,
+            location(item),
+        ),
+        None => writeln!(w, r"<li{}><code>{tokens}</code></li>", location(item)),
+    }
+}
+
+/// Prints the old and the new version of `changed_item` side by side, with the
+/// tokens that differ highlighted.
+fn print_changed_item(w: &mut dyn Write, changed_item: &ChangedPublicItem) -> Result<()> {
+    let old_tokens: Vec<&Token> = changed_item.old.tokens().collect();
+    let new_tokens: Vec<&Token> = changed_item.new.tokens().collect();
+    let mut old = String::new();
+    let mut new = String::new();
+    for diff_result in diff::slice(old_tokens.as_slice(), new_tokens.as_slice()) {
+        match diff_result {
+            diff::Result::Left(token) => old.push_str(&highlight(token, Some("removed-token"))),
+            diff::Result::Both(token, _) => {
+                old.push_str(&highlight(token, None));
+                new.push_str(&highlight(token, None));
+            }
+            diff::Result::Right(token) => new.push_str(&highlight(token, Some("added-token"))),
+        }
+    }
+
+    writeln!(
+        w,
+        r#"<li class="changed"{}><span class="marker">~</span><table><tr><td><code>{old}</code></td><td><code>{new}</code></td></tr></table></li>"#,
+        location(&changed_item.new),
+    )
+}
+
+/// Wraps `token` in a `<span>` with the class of its color, or with
+/// `diff_class` if it is a token that differs between an old and a new item.
+fn highlight(token: &Token, diff_class: Option<&str>) -> String {
+    let class = diff_class.or_else(|| match token_color(token)? {
+        Color::Blue => Some("blue"),
+        Color::Cyan => Some("cyan"),
+        Color::Yellow => Some("yellow"),
+        Color::Green => Some("green"),
+        _ => None,
+    });
+
+    match class {
+        Some(class) => format!(r#"<span class="{class}">{}</span>"#, escape(token.text())),
+        None => escape(token.text()),
+    }
+}
+
+/// A `title` attribute with where the item is defined, shown when hovering
+/// the item.
+fn location(item: &PublicItem) -> String {
+    item.span().map_or_else(String::new, |span| {
+        format!(r#" title="{}""#, escape(&span.to_string()))
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,141 @@
+use std::io::{Result, Write};
+
+use public_api::{diff::PublicItemsDiff, PublicItem};
+
+use crate::arg_types::DenyMethod;
+use crate::Args;
+
+/// Prints a `JUnit` XML report, which most CI systems can show as test results.
+pub struct Junit;
+
+/// A changed item, reported as a testcase.
+struct TestCase {
+    /// The path of the item, e.g. `example_api::function`.
+    name: String,
+
+    /// The kind of change, e.g. `removed`.
+    kind: &'static str,
+
+    /// The rendered old and new item, as e.g. `-pub fn f()\n+pub fn f(x: usize)`.
+    rendered: String,
+
+    /// Whether the change is denied by `--deny`, i.e. whether the testcase
+    /// fails.
+    denied: bool,
+}
+
+impl Junit {
+    /// Prints one testsuite for `crate_name` with one testcase per changed
+    /// item. Testcases of changes denied by `--deny` fail, other testcases
+    /// pass.
+    pub fn print_diff(
+        w: &mut dyn Write,
+        args: &Args,
+        crate_name: &str,
+        diff: &PublicItemsDiff,
+    ) -> Result<()> {
+        let mut test_cases = vec![];
+        for item in &diff.removed {
+            test_cases.push(TestCase {
+                name: path(item),
+                kind: "removed",
+                rendered: format!("-{item}"),
+                denied: args.denies(DenyMethod::deny_removed),
+            });
+        }
+        for changed_item in &diff.changed {
+            test_cases.push(TestCase {
+                name: path(&changed_item.new),
+                kind: "changed",
+                rendered: format!("-{}\n+{}", changed_item.old, changed_item.new),
+                denied: args.denies(DenyMethod::deny_changed),
+            });
+        }
+        for item in &diff.added {
+            test_cases.push(TestCase {
+                name: path(item),
+                kind: "added",
+                rendered: format!("+{item}"),
+                denied: args.denies(DenyMethod::deny_added),
+            });
+        }
+
+        let tests = test_cases.len();
+        let failures = test_cases.iter().filter(|t| t.denied).count();
+        let crate_name = escape(crate_name);
+
+        writeln!(w, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(
+            w,
+            r#"<testsuites name="public API" tests="{tests}" failures="{failures}" errors="0">"#
+        )?;
+        writeln!(
+            w,
+            r#"  <testsuite name="{crate_name}" tests="{tests}" failures="{failures}" errors="0" skipped="0">"#
+        )?;
+        for test_case in test_cases {
+            let name = escape(&test_case.name);
+            let kind = test_case.kind;
+            if test_case.denied {
+                writeln!(
+                    w,
+                    r#"    <testcase name="{name}" classname="{crate_name}.{kind}">"#
+                )?;
+                writeln!(
+                    w,
+                    r#"      <failure message="{}" type="{kind}">{}</failure>"#,
+                    escape(&format!(
+                        "{} item not allowed as per --deny:\n{}",
+                        capitalized(kind),
+                        test_case.rendered
+                    )),
+                    escape(&test_case.rendered),
+                )?;
+                writeln!(w, "    </testcase>")?;
+            } else {
+                writeln!(
+                    w,
+                    r#"    <testcase name="{name}" classname="{crate_name}.{kind}"/>"#
+                )?;
+            }
+        }
+        writeln!(w, "  </testsuite>")?;
+        writeln!(w, "</testsuites>")
+    }
+}
+
+fn path(item: &PublicItem) -> String {
+    item.path().join("::")
+}
+
+fn capitalized(kind: &str) -> String {
+    let mut chars = kind.chars();
+    chars
+        .next()
+        .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+        .unwrap_or_default()
+}
+
+/// Escapes text so that it can be put both in XML attribute values and between
+/// XML tags. Newlines are escaped so that they survive in attribute values.
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::escape;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(
+            escape("pub fn f<'a>(x: &'a str) -> Box<dyn Fn() + \"a\">\n"),
+            "pub fn f&lt;&apos;a&gt;(x: &amp;&apos;a str) -&gt; Box&lt;dyn Fn() + &quot;a&quot;&gt;&#10;"
+        );
+    }
+}
@@ -1,24 +1,39 @@
 // deny in CI, only warn here
 #![warn(clippy::all, clippy::pedantic)]
 
-use std::io::stdout;
+use std::io::{stderr, stdout};
 use std::path::{Path, PathBuf};
 
 use anyhow::{anyhow, Context, Result};
-use arg_types::{Color, DenyMethod};
+use arg_types::{Color, DenyMethod, OutputFormat};
+use feature_matrix::FeatureMatrixDiff;
+use github::Github;
+use html::Html;
+use junit::Junit;
 use plain::Plain;
 use public_api::diff::PublicItemsDiff;
 use public_api::{
-    public_api_from_rustdoc_json_str, Options, PublicApi, PublicItem, MINIMUM_RUSTDOC_JSON_VERSION,
+    leaks::leaked_types_from_rustdoc_json_str, Options, PublicApi, PublicItem,
+    MINIMUM_RUSTDOC_JSON_VERSION,
 };
+use sarif::Sarif;
+use target_matrix::TargetMatrixDiff;
 
 use clap::Parser;
 use rustdoc_json::{BuildError, BuildOptions};
 
+mod api_matrix;
 mod arg_types;
 mod error;
+mod feature_matrix;
 mod git_utils;
+mod github;
+mod html;
+mod junit;
 mod plain;
+mod sarif;
+mod target_matrix;
+mod tree;
 
 #[derive(Parser, Debug)]
 #[clap(author, version, about, long_about = None)]
@@ -40,7 +55,28 @@ pub struct Args {
     #[clap(long)]
     with_blanket_implementations: bool,
 
-    /// Usage: --diff-git-checkouts <COMMIT_1> <COMMIT_2>
+    /// Raise this flag to also list a `pub use` line for each re-exported item,
+    /// such as `pub use other_crate::Thing as your_crate::Alias`. This shows
+    /// where re-exported items are originally defined, which makes it easy to
+    /// see what parts of the public API that come from dependencies.
+    #[clap(long)]
+    with_reexports: bool,
+
+    /// Raise this flag to show where in the source code each listed or diffed
+    /// item is defined, as e.g. `// src/lib.rs:42` after the item. For removed
+    /// items, the location is in the old version of the code.
+    #[clap(long)]
+    show_locations: bool,
+
+    /// Raise this flag to nest items under their modules and types, with each
+    /// item shown relative to its parent, e.g. `pub fn f()` under `pub mod
+    /// your_crate::a` instead of `pub fn your_crate::a::f()`. Modules and types
+    /// are annotated with how many items they contain. When diffing, changed
+    /// items are nested the same way and marked with `-`, `~` or `+`.
+    #[clap(long)]
+    tree: bool,
+
+    /// Usage: `--diff-git-checkouts <COMMIT_1> <COMMIT_2>`
     ///
     /// Allows to diff the public API across two different commits. The
     /// following steps are performed:
@@ -72,7 +108,7 @@ pub struct Args {
     #[clap(long, min_values = 2, max_values = 2)]
     diff_git_checkouts: Option<Vec<String>>,
 
-    /// Usage: --diff-rustdoc-json <RUSTDOC_JSON_PATH_1> <RUSTDOC_JSON_PATH_2>
+    /// Usage: `--diff-rustdoc-json <RUSTDOC_JSON_PATH_1> <RUSTDOC_JSON_PATH_2>`
     ///
     /// Diff the public API across two different rustdoc JSON files.
     #[clap(long, min_values = 2, max_values = 2, hide = true)]
@@ -99,6 +135,28 @@ pub struct Args {
     #[clap(long, default_value = "auto")]
     color: Color,
 
+    /// How to print the public API or the diff.
+    ///
+    /// * plain = human readable text
+    ///
+    /// * github = GitHub Actions workflow commands, so that changed items are
+    ///   annotated inline on the PR diff. Items denied by `--deny` become
+    ///   errors, other changes become notices. Only for diffs.
+    ///
+    /// * sarif = a SARIF 2.1.0 log with one result per changed item, for
+    ///   tools that ingest static analysis results. Items denied by `--deny`
+    ///   are errors, other changes are notes. Only for diffs.
+    ///
+    /// * junit = a `JUnit` XML report with a testsuite for the crate and a
+    ///   testcase for each changed item. Testcases of items denied by `--deny`
+    ///   fail, other testcases pass. Only for diffs.
+    ///
+    /// * html = a self-contained HTML page, with items grouped into a
+    ///   collapsible tree, and a search box. Changed items are shown side by
+    ///   side.
+    #[clap(long, arg_enum, default_value = "plain")]
+    output_format: OutputFormat,
+
     /// Show detailed info about processing. For debugging purposes. The output
     /// is not stable and can change across patch versions.
     #[clap(long, hide = true)]
@@ -114,8 +172,14 @@ pub struct Args {
     toolchain: Option<String>,
 
     /// Build for the target triple
+    ///
+    /// Can be given several times. Then rustdoc JSON is built once for each
+    /// target, and the public API is listed with each item that does not exist
+    /// on all targets annotated with the targets it exists on. Combined with
+    /// `--diff-git-checkouts`, the public API of each target is diffed
+    /// separately and changes are annotated with the targets they affect.
     #[clap(long)]
-    target: Option<String>,
+    target: Vec<String>,
 
     /// Space or comma separated list of features to activate
     #[clap(long, short = 'F', min_values = 1)]
@@ -132,6 +196,44 @@ pub struct Args {
     /// Package to document
     #[clap(long, short)]
     package: Option<String>,
+
+    /// Build rustdoc JSON once without features, once with default features,
+    /// once for each feature individually, and once with all features. Then
+    /// list the public API where each item is annotated with the features
+    /// that enable it. Items without annotation are always available.
+    ///
+    /// Can be combined with `--diff-git-checkouts`. In addition to the regular
+    /// diff, that will also list items that now require features, which
+    /// breaks users that depend on the crate with `default-features = false`,
+    /// as well as items that are no longer enabled by default. With
+    /// `--deny=removed`, items that now require features count as removed.
+    #[clap(
+        long,
+        conflicts_with_all = &["features", "all-features", "no-default-features", "diff-rustdoc-json"]
+    )]
+    feature_matrix: bool,
+
+    /// List types from other crates that are part of the public API, grouped
+    /// by crate. Such types tie your semver to the semver of those crates.
+    /// Types from the standard library are not listed.
+    #[clap(
+        long,
+        conflicts_with_all = &["diff-git-checkouts", "diff-rustdoc-json", "feature-matrix"]
+    )]
+    leaked_types: bool,
+
+    /// Usage: `--deny-leaked-types[=<ALLOWED_CRATE>,...]`
+    ///
+    /// Exit with failure if types from other crates than the given allowed
+    /// crates are part of the public API. Without allowed crates, any leaked
+    /// type is a failure. Requires `--leaked-types`.
+    #[clap(
+        long,
+        min_values = 0,
+        use_value_delimiter = true,
+        requires = "leaked-types"
+    )]
+    deny_leaked_types: Option<Vec<String>>,
 }
 
 /// After listing or diffing, we might want to do some extra work. This struct
@@ -161,10 +263,59 @@ fn main_() -> Result<()> {
         args.toolchain = Some("+nightly".to_owned());
     }
 
+    if args.target.len() > 1 && (args.feature_matrix || args.diff_rustdoc_json.is_some()) {
+        return Err(anyhow!(
+            "Several `--target`s can not be combined with `--feature-matrix` or `--diff-rustdoc-json`"
+        ));
+    }
+
+    let diffing = args.diff_git_checkouts.is_some() || args.diff_rustdoc_json.is_some();
+    if args.output_format != OutputFormat::Plain
+        && (args.feature_matrix || args.target.len() > 1 || args.leaked_types)
+    {
+        return Err(anyhow!(
+            "`--output-format {}` can not be used with `--feature-matrix`, several `--target`s, or `--leaked-types`",
+            args.output_format.name()
+        ));
+    }
+    if args.tree
+        && (args.output_format != OutputFormat::Plain
+            || args.feature_matrix
+            || args.target.len() > 1
+            || args.leaked_types)
+    {
+        return Err(anyhow!(
+            "`--tree` can not be used with `--output-format {}`, `--feature-matrix`, several `--target`s, or `--leaked-types`",
+            args.output_format.name()
+        ));
+    }
+    if args.output_format.only_for_diffs() && !diffing {
+        return Err(anyhow!(
+            "`--output-format {}` can only be used when diffing",
+            args.output_format.name()
+        ));
+    }
+
+    if !args.target.is_empty() && args.diff_rustdoc_json.is_none() {
+        warn_if_target_is_ignored(&args);
+    }
+
     let post_processing = if let Some(commits) = &args.diff_git_checkouts {
-        print_diff_between_two_commits(&args, commits)?
+        if args.feature_matrix {
+            print_feature_matrix_diff_between_two_commits(&args, commits)?
+        } else if args.target.len() > 1 {
+            print_target_matrix_diff_between_two_commits(&args, commits)?
+        } else {
+            print_diff_between_two_commits(&args, commits)?
+        }
     } else if let Some(files) = &args.diff_rustdoc_json {
         print_diff_between_two_rustdoc_json_files(&args, files)?
+    } else if args.leaked_types {
+        print_leaked_types_of_current_commit(&args)?
+    } else if args.feature_matrix {
+        print_feature_matrix_of_current_commit(&args)?
+    } else if args.target.len() > 1 {
+        print_target_matrix_of_current_commit(&args)?
     } else {
         print_public_items_of_current_commit(&args)?
     };
@@ -172,6 +323,16 @@ fn main_() -> Result<()> {
     post_processing.perform(&args)
 }
 
+/// Proc-macro crates are always built for the host, so `--target` is ignored
+/// for them when building rustdoc JSON. Let the user know.
+fn warn_if_target_is_ignored(args: &Args) {
+    if let Ok(true) = rustdoc_json::is_proc_macro(&args.manifest_path, args.package.as_deref()) {
+        eprintln!(
+            "Warning: ignoring `--target` since proc-macro crates are always built for the host"
+        );
+    }
+}
+
 /// Returns true if it seems like the currently active toolchain is the stable
 /// toolchain.
 ///
@@ -221,26 +382,32 @@ fn active_toolchain_is_environment_override() -> Option<String> {
         })
 }
 
-fn check_diff(args: &Args, diff: &Option<PublicItemsDiff>) -> Result<()> {
+fn check_diff(args: &Args, diff: Option<&PublicItemsDiff>) -> Result<()> {
     match (&args.deny, diff) {
         // We were requested to deny diffs, so make sure there is no diff
-        (Some(deny), Some(diff)) => {
+        (Some(_), Some(diff)) => {
             let mut violations = crate::error::Violations::new();
-            for d in deny {
-                if d.deny_added() && !diff.added.is_empty() {
-                    violations.extend_added(diff.added.iter().cloned());
-                }
-                if d.deny_changed() && !diff.changed.is_empty() {
-                    violations.extend_changed(diff.changed.iter().cloned());
-                }
-                if d.deny_removed() && !diff.removed.is_empty() {
-                    violations.extend_removed(diff.removed.iter().cloned());
-                }
+            if args.denies(DenyMethod::deny_added) {
+                violations.extend_added(diff.added.iter().cloned());
+            }
+            if args.denies(DenyMethod::deny_changed) {
+                violations.extend_changed(diff.changed.iter().cloned());
+            }
+            if args.denies(DenyMethod::deny_removed) {
+                violations.extend_removed(diff.removed.iter().cloned());
             }
 
             if violations.is_empty() {
                 Ok(())
             } else {
+                // The plain diff on stdout already shows the violations. Other
+                // output formats are harder to read, so then we list the
+                // violations on stderr, next to the error
+                if args.output_format == OutputFormat::Plain {
+                    Plain::print_deny_hint(&mut stderr(), args)?;
+                } else {
+                    Plain::print_violations(&mut stderr(), args, &violations)?;
+                }
                 Err(anyhow!(error::Error::DiffDenied(violations)))
             }
         }
@@ -256,22 +423,47 @@ fn check_diff(args: &Args, diff: &Option<PublicItemsDiff>) -> Result<()> {
 fn print_public_items_of_current_commit(args: &Args) -> Result<PostProcessing> {
     let (public_items, branch_to_restore) = collect_public_api_from_commit(args, None)?;
 
-    if args.verbose {
-        public_items.missing_item_ids.iter().for_each(|i| {
-            println!("NOTE: rustdoc JSON missing referenced item with ID \"{i}\"");
-        });
+    match args.output_format {
+        OutputFormat::Html => Html::print_items(
+            &mut stdout(),
+            &crate_name(&public_items.items),
+            &public_items.items,
+        )?,
+        _ if args.tree => Plain::print_items_tree(&mut stdout(), args, &public_items.items)?,
+        _ => Plain::print_items(&mut stdout(), args, public_items.items)?,
     }
 
-    Plain::print_items(&mut stdout(), args, public_items.items)?;
-
     Ok(PostProcessing {
         diff_to_check: None,
         branch_to_restore,
     })
 }
 
+fn print_leaked_types_of_current_commit(args: &Args) -> Result<PostProcessing> {
+    let json_path = build_rustdoc_json(args, build_options(args))?;
+    let rustdoc_json = &std::fs::read_to_string(&json_path)
+        .with_context(|| format!("Failed to read rustdoc JSON at {}", json_path.display()))?;
+    let leaked_types = leaked_types_from_rustdoc_json_str(rustdoc_json, get_options(args))?;
+
+    Plain::print_leaked_types(&mut stdout(), args, &leaked_types)?;
+
+    if let Some(allowed) = &args.deny_leaked_types {
+        let denied = leaked_types.not_allowed(allowed);
+        if !denied.is_empty() {
+            return Err(anyhow!(error::Error::LeakedTypesDenied(
+                denied.into_iter().map(|c| c.name.clone()).collect()
+            )));
+        }
+    }
+
+    Ok(PostProcessing {
+        diff_to_check: None,
+        branch_to_restore: None,
+    })
+}
+
 fn print_diff_between_two_commits(args: &Args, commits: &[String]) -> Result<PostProcessing> {
-    let old_commit = commits.get(0).expect("clap makes sure first commit exist");
+    let old_commit = commits.first().expect("clap makes sure first commit exist");
     let (old, branch_to_restore) = collect_public_api_from_commit(args, Some(old_commit))?;
 
     let new_commit = commits.get(1).expect("clap makes sure second commit exist");
@@ -285,17 +477,100 @@ fn print_diff_between_two_commits(args: &Args, commits: &[String]) -> Result<Pos
     })
 }
 
+fn print_feature_matrix_of_current_commit(args: &Args) -> Result<PostProcessing> {
+    let matrix = feature_matrix::collect(args)?;
+
+    Plain::print_feature_matrix(&mut stdout(), args, &matrix)?;
+
+    Ok(PostProcessing {
+        diff_to_check: None,
+        branch_to_restore: None,
+    })
+}
+
+fn print_feature_matrix_diff_between_two_commits(
+    args: &Args,
+    commits: &[String],
+) -> Result<PostProcessing> {
+    let old_commit = commits.first().expect("clap makes sure first commit exist");
+    let branch_to_restore = git_checkout_commit(args, Some(old_commit))?;
+    let old = feature_matrix::collect(args)?;
+
+    let new_commit = commits.get(1).expect("clap makes sure second commit exist");
+    git_checkout_commit(args, Some(new_commit))?;
+    let new = feature_matrix::collect(args)?;
+
+    let feature_matrix_diff = FeatureMatrixDiff::between(&old, &new);
+    Plain::print_feature_matrix_diff(&mut stdout(), args, &new, &feature_matrix_diff)?;
+
+    // Items that now require features are removed from the public API of
+    // `default-features = false` users
+    let mut diff_to_check = feature_matrix_diff.diff;
+    diff_to_check.removed.extend(
+        feature_matrix_diff
+            .now_require_features
+            .into_iter()
+            .cloned(),
+    );
+    diff_to_check.removed.sort();
+
+    Ok(PostProcessing {
+        diff_to_check: Some(diff_to_check),
+        branch_to_restore,
+    })
+}
+
+fn print_target_matrix_of_current_commit(args: &Args) -> Result<PostProcessing> {
+    let matrix = target_matrix::collect(args)?;
+
+    Plain::print_target_matrix(&mut stdout(), args, &matrix, &args.target)?;
+
+    Ok(PostProcessing {
+        diff_to_check: None,
+        branch_to_restore: None,
+    })
+}
+
+fn print_target_matrix_diff_between_two_commits(
+    args: &Args,
+    commits: &[String],
+) -> Result<PostProcessing> {
+    let old_commit = commits.first().expect("clap makes sure first commit exist");
+    let branch_to_restore = git_checkout_commit(args, Some(old_commit))?;
+    let old = target_matrix::collect(args)?;
+
+    let new_commit = commits.get(1).expect("clap makes sure second commit exist");
+    git_checkout_commit(args, Some(new_commit))?;
+    let new = target_matrix::collect(args)?;
+
+    let target_matrix_diff = TargetMatrixDiff::between(&old, &new, &args.target);
+    Plain::print_target_matrix_diff(&mut stdout(), args, &target_matrix_diff)?;
+
+    Ok(PostProcessing {
+        diff_to_check: Some(target_matrix_diff.diff_to_check()),
+        branch_to_restore,
+    })
+}
+
 fn print_diff_between_two_rustdoc_json_files(
     args: &Args,
     files: &[String],
 ) -> Result<PostProcessing> {
-    let options = get_options(args);
-
-    let old_file = files.get(0).expect("clap makes sure first file exists");
-    let old = public_api_from_rustdoc_json_path(old_file, options)?;
+    // Both files are processed at the same time, which is noticeably faster
+    // for big crates
+    let mut public_apis = public_api::public_api_from_rustdoc_json_paths(files, get_options(args))
+        .into_iter()
+        .zip(files)
+        .map(|(result, file)| {
+            with_rustdoc_json_context(result, format!("the rustdoc JSON at {file:?}"))
+        });
 
-    let new_file = files.get(1).expect("clap makes sure second file exists");
-    let new = public_api_from_rustdoc_json_path(new_file, options)?;
+    let old = public_apis
+        .next()
+        .expect("clap makes sure first file exists")?;
+    let new = public_apis
+        .next()
+        .expect("clap makes sure second file exists")?;
 
     let diff_to_check = Some(print_diff(args, old.items, new.items)?);
 
@@ -306,19 +581,37 @@ fn print_diff_between_two_rustdoc_json_files(
 }
 
 fn print_diff(args: &Args, old: Vec<PublicItem>, new: Vec<PublicItem>) -> Result<PublicItemsDiff> {
+    let crate_name = crate_name(new.iter().chain(&old));
+
     let diff = PublicItemsDiff::between(old, new);
-    Plain::print_diff(&mut stdout(), args, &diff)?;
+    match args.output_format {
+        OutputFormat::Plain if args.tree => Plain::print_diff_tree(&mut stdout(), args, &diff)?,
+        OutputFormat::Plain => Plain::print_diff(&mut stdout(), args, &diff)?,
+        OutputFormat::Github => Github::print_diff(&mut stdout(), args, &diff)?,
+        OutputFormat::Sarif => Sarif::print_diff(&mut stdout(), args, &diff)?,
+        OutputFormat::Junit => Junit::print_diff(&mut stdout(), args, &crate_name, &diff)?,
+        OutputFormat::Html => Html::print_diff(&mut stdout(), &crate_name, &diff)?,
+    }
 
     Ok(diff)
 }
 
+/// The first path component of any item, such as the root module, is the name
+/// of the crate.
+fn crate_name<'a>(items: impl IntoIterator<Item = &'a PublicItem>) -> String {
+    items
+        .into_iter()
+        .find_map(|item| item.path().first().cloned())
+        .unwrap_or_default()
+}
+
 impl PostProcessing {
     fn perform(&self, args: &Args) -> Result<()> {
         if let Some(branch_to_restore) = &self.branch_to_restore {
             git_utils::git_checkout(branch_to_restore, &args.git_root()?, !args.verbose)?;
         }
 
-        check_diff(args, &self.diff_to_check)
+        check_diff(args, self.diff_to_check.as_ref())
     }
 }
 
@@ -326,6 +619,24 @@ impl Args {
     fn git_root(&self) -> Result<PathBuf> {
         git_utils::git_root_from_manifest_path(self.manifest_path.as_path())
     }
+
+    /// The spans in rustdoc JSON are relative to the package, but tools that
+    /// annotate source code want paths relative to the root of the repository.
+    /// Returns the path of the package relative to the git root, if there is a
+    /// git root.
+    fn package_dir_in_git_root(&self) -> Option<PathBuf> {
+        let git_root = self.git_root().ok()?;
+        let manifest_path = std::fs::canonicalize(&self.manifest_path).ok()?;
+        let package_dir = manifest_path.parent()?.strip_prefix(git_root).ok()?;
+
+        Some(package_dir.to_owned())
+    }
+
+    /// Whether `--deny` denies the kind of change that `deny_kind` checks for,
+    /// e.g. `args.denies(DenyMethod::deny_removed)`.
+    fn denies(&self, deny_kind: fn(DenyMethod) -> bool) -> bool {
+        self.deny.iter().flatten().any(|d| deny_kind(*d))
+    }
 }
 
 /// Get CLI args via `clap` while also handling when we are invoked as a cargo
@@ -346,6 +657,7 @@ fn get_args() -> Args {
 fn get_options(args: &Args) -> Options {
     let mut options = Options::default();
     options.with_blanket_implementations = args.with_blanket_implementations;
+    options.with_reexports = args.with_reexports;
     options
 }
 
@@ -356,24 +668,39 @@ fn collect_public_api_from_commit(
     args: &Args,
     commit: Option<&str>,
 ) -> Result<(PublicApi, Option<String>)> {
-    // Do a git checkout of a specific commit unless we are supposed to simply
-    // use the current commit
-    let original_branch = if let Some(commit) = commit {
-        Some(git_utils::git_checkout(
+    let original_branch = git_checkout_commit(args, commit)?;
+
+    Ok((
+        collect_public_api(args, build_options(args))?,
+        original_branch,
+    ))
+}
+
+/// Do a git checkout of a specific commit unless we are supposed to simply use
+/// the current commit. Returns the original branch if a checkout was made.
+fn git_checkout_commit(args: &Args, commit: Option<&str>) -> Result<Option<String>> {
+    if let Some(commit) = commit {
+        Ok(Some(git_utils::git_checkout(
             commit,
             &args.git_root()?,
             !args.verbose,
-        )?)
+        )?))
     } else {
-        None
-    };
+        Ok(None)
+    }
+}
+
+/// The [`BuildOptions`] to use for building rustdoc JSON, as specified by our
+/// [`Args`].
+fn build_options(args: &Args) -> BuildOptions {
     let mut build_options = BuildOptions::default()
         .toolchain(args.toolchain.clone())
         .manifest_path(&args.manifest_path)
         .all_features(args.all_features)
         .no_default_features(args.no_default_features)
         .features(&args.features);
-    if let Some(target) = &args.target {
+    // With several targets, the target is set for each build separately
+    if let [target] = args.target.as_slice() {
         build_options = build_options.target(target.clone());
     }
 
@@ -381,44 +708,86 @@ fn collect_public_api_from_commit(
         build_options = build_options.package(package);
     }
 
+    build_options
+}
+
+/// Builds rustdoc JSON with the given [`BuildOptions`] and collects the public
+/// API from it.
+fn collect_public_api(args: &Args, build_options: BuildOptions) -> Result<PublicApi> {
+    let result = PublicApi::builder()
+        .build_options(build_options)
+        .options(get_options(args))
+        .build_with_rustdoc_json_path()
+        .map(|(public_api, json_path)| {
+            if args.verbose {
+                println!("Processing {}", json_path.display());
+            }
+            public_api
+        });
+    let public_api = match result {
+        Err(public_api::Error::BuildError(BuildError::VirtualManifest(manifest_path))) => {
+            virtual_manifest_error(&manifest_path)?
+        }
+        Err(public_api::Error::BuildError(e)) => return Err(e.into()),
+        result => with_rustdoc_json_context(
+            result,
+            format!("the rustdoc JSON of {}", args.manifest_path.display()),
+        )?,
+    };
+    if args.verbose {
+        for warning in &public_api.warnings {
+            println!("NOTE: {warning}");
+        }
+    }
+
+    Ok(public_api)
+}
+
+/// Builds rustdoc JSON with the given [`BuildOptions`] and returns its path.
+/// Only needed where we want the rustdoc JSON itself rather than a
+/// [`PublicApi`], which [`collect_public_api`] gives us.
+fn build_rustdoc_json(args: &Args, build_options: BuildOptions) -> Result<PathBuf> {
     let json_path = match rustdoc_json::build(build_options) {
         Err(BuildError::VirtualManifest(manifest_path)) => virtual_manifest_error(&manifest_path)?,
         res => res?,
     };
     if args.verbose {
-        println!("Processing {:?}", json_path);
+        println!("Processing {}", json_path.display());
     }
-    let options = get_options(args);
 
-    Ok((
-        public_api_from_rustdoc_json_path(json_path, options)?,
-        original_branch,
-    ))
+    Ok(json_path)
 }
 
-fn public_api_from_rustdoc_json_path<T: AsRef<Path>>(
-    json_path: T,
-    options: Options,
+/// Explains what went wrong, and what to do about it, if collecting the public
+/// API from `rustdoc_json` failed.
+fn with_rustdoc_json_context(
+    result: public_api::Result<PublicApi>,
+    rustdoc_json: impl std::fmt::Display,
 ) -> Result<PublicApi> {
-    let rustdoc_json = &std::fs::read_to_string(&json_path)
-        .with_context(|| format!("Failed to read rustdoc JSON at {:?}", json_path.as_ref()))?;
+    if let Err(public_api::Error::IoError(e)) = result {
+        return Err(anyhow!(e)).with_context(|| format!("Failed to read {rustdoc_json}"));
+    }
+
+    // The error message of an unsupported format version is precise enough, so
+    // there is no need to guess what went wrong
+    if let Err(e @ public_api::Error::UnsupportedFormatVersion { .. }) = result {
+        return Err(anyhow!(e)).with_context(|| format!("Failed to parse {rustdoc_json}"));
+    }
 
-    public_api_from_rustdoc_json_str(rustdoc_json, options).with_context(|| {
+    result.with_context(|| {
         format!(
-            "Failed to parse rustdoc JSON at {:?}.\n\
-            This version of `cargo public-api` requires at least:\n\n    {}\n\n\
+            "Failed to parse {rustdoc_json}.\n\
+            This version of `cargo public-api` requires at least:\n\n    {MINIMUM_RUSTDOC_JSON_VERSION}\n\n\
             If you have that, it might be `cargo public-api` that is out of date. Try\n\
             to install the latest version with `cargo install cargo-public-api`. If the\n\
-            issue remains, please report at\n\n    https://github.com/Enselic/cargo-public-api/issues",
-            json_path.as_ref(),
-            MINIMUM_RUSTDOC_JSON_VERSION,
+            issue remains, please report at\n\n    https://github.com/Enselic/cargo-public-api/issues"
         )
     })
 }
 
-fn virtual_manifest_error(manifest_path: &Path) -> Result<PathBuf> {
+fn virtual_manifest_error<T>(manifest_path: &Path) -> Result<T> {
     Err(anyhow!(
-        "`{:?}` is a virtual manifest.
+        "`{}` is a virtual manifest.
 
 Listing or diffing the public API of an entire workspace is not supported.
 
@@ -426,7 +795,7 @@ Try
 
     cargo public-api -p specific-crate
 ",
-        manifest_path
+        manifest_path.display()
     ))
 }
 
@@ -1,40 +1,40 @@
 use std::io::{Result, Write};
 
 use nu_ansi_term::{AnsiString, AnsiStrings, Color, Style};
-use public_api::{diff::PublicItemsDiff, tokens::Token, PublicItem};
+use public_api::{
+    diff::{ChangedPublicItem, PublicItemsDiff},
+    leaks::LeakedTypes,
+    tokens::Token,
+    PublicItem,
+};
 
+use crate::api_matrix::ApiMatrix;
+use crate::error::Violations;
+use crate::feature_matrix::{self, FeatureMatrixDiff};
+use crate::target_matrix::{self, TargetMatrixDiff};
+use crate::tree::{self, Entry, Node};
 use crate::Args;
 
 pub struct Plain;
 
 impl Plain {
     pub fn print_items(w: &mut dyn Write, args: &Args, items: Vec<PublicItem>) -> Result<()> {
+        let colored = args.color.active();
         for item in items {
-            if args.color.active() {
-                writeln!(w, "{}", color_item(&item))?;
-            } else {
-                writeln!(w, "{}", item)?;
-            }
+            print_annotated_item(w, args, colored, "", &item, None)?;
         }
 
         Ok(())
     }
 
     pub fn print_diff(w: &mut dyn Write, args: &Args, diff: &PublicItemsDiff) -> Result<()> {
-        let use_color = args.color.active();
-
+        let colored = args.color.active();
         print_items_with_header(
             w,
             "Removed items from the public API\n\
              =================================",
             &diff.removed,
-            |w, item| {
-                if use_color {
-                    writeln!(w, "-{}", color_item(item))
-                } else {
-                    writeln!(w, "-{}", item)
-                }
-            },
+            |w, item| print_annotated_item(w, args, colored, "-", item, None),
         )?;
 
         print_items_with_header(
@@ -42,21 +42,7 @@ impl Plain {
             "Changed items in the public API\n\
              ===============================",
             &diff.changed,
-            |w, changed_item| {
-                if use_color {
-                    let old_tokens: Vec<&Token> = changed_item.old.tokens().collect();
-                    let new_tokens: Vec<&Token> = changed_item.new.tokens().collect();
-                    let diff_slice = diff::slice(old_tokens.as_slice(), new_tokens.as_slice());
-                    writeln!(
-                        w,
-                        "-{}\n+{}",
-                        color_item_with_diff(&diff_slice, true),
-                        color_item_with_diff(&diff_slice, false),
-                    )
-                } else {
-                    writeln!(w, "-{}\n+{}", changed_item.old, changed_item.new)
-                }
-            },
+            |w, changed_item| print_changed_item(w, args, colored, changed_item, None),
         )?;
 
         print_items_with_header(
@@ -64,17 +50,425 @@ impl Plain {
             "Added items to the public API\n\
              =============================",
             &diff.added,
-            |w, item| {
-                if use_color {
-                    writeln!(w, "+{}", color_item(item))
+            |w, item| print_annotated_item(w, args, colored, "+", item, None),
+        )?;
+
+        Ok(())
+    }
+
+    /// Like [`Self::print_items`], but with items nested under their modules
+    /// and types, and shown relative to them.
+    pub fn print_items_tree(w: &mut dyn Write, args: &Args, items: &[PublicItem]) -> Result<()> {
+        print_tree(w, args, &Node::from_items(items), "item")
+    }
+
+    /// Like [`Self::print_diff`], but with changed items nested under their
+    /// modules and types, and marked with `-`, `~` or `+`.
+    pub fn print_diff_tree(w: &mut dyn Write, args: &Args, diff: &PublicItemsDiff) -> Result<()> {
+        if diff.removed.is_empty() && diff.changed.is_empty() && diff.added.is_empty() {
+            return writeln!(w, "(no changes)");
+        }
+
+        print_tree(w, args, &Node::from_diff(diff), "change")
+    }
+
+    /// Lists the items that `--deny` does not allow, grouped like in
+    /// [`Self::print_diff`], followed by [`Self::print_deny_hint`]. Meant for
+    /// stderr, when stdout does not already show the diff in plain text.
+    pub fn print_violations(w: &mut dyn Write, args: &Args, violations: &Violations) -> Result<()> {
+        let colored = args.color.active_for(atty::Stream::Stderr);
+
+        print_violations_with_header(w, "Removed", violations.removed(), |w, item| {
+            print_annotated_item(w, args, colored, "-", item, None)
+        })?;
+
+        print_violations_with_header(w, "Changed", violations.changed(), |w, changed_item| {
+            print_changed_item(w, args, colored, changed_item, None)
+        })?;
+
+        print_violations_with_header(w, "Added", violations.added(), |w, item| {
+            print_annotated_item(w, args, colored, "+", item, None)
+        })?;
+
+        Self::print_deny_hint(w, args)
+    }
+
+    /// Prints a hint on how to allow the changes that `--deny` does not allow,
+    /// either by changing `--deny`, or by blessing the changes. Meant for
+    /// stderr, so it is bold if stderr is a terminal.
+    pub fn print_deny_hint(w: &mut dyn Write, args: &Args) -> Result<()> {
+        let deny_args: Vec<String> = args
+            .deny
+            .iter()
+            .flatten()
+            .map(|d| format!("`--deny={}`", d.name()))
+            .collect();
+        let hint = format!(
+            "Hint: If the changes are intended, allow them by dropping or narrowing {}, \
+             or bless them as described in \
+             https://github.com/Enselic/cargo-public-api/blob/main/docs/CI-EXAMPLES.md#with-a-changeable-public-api",
+            deny_args.join(" and ")
+        );
+        if args.color.active_for(atty::Stream::Stderr) {
+            writeln!(w, "{}", Style::new().bold().paint(hint))?;
+        } else {
+            writeln!(w, "{hint}")?;
+        }
+
+        writeln!(w)
+    }
+
+    pub fn print_leaked_types(
+        w: &mut dyn Write,
+        args: &Args,
+        leaked_types: &LeakedTypes,
+    ) -> Result<()> {
+        if leaked_types.is_empty() {
+            writeln!(w, "(no leaked types)")?;
+        }
+
+        for leaking_crate in &leaked_types.crates {
+            let header = format!("Types leaked from {}", leaking_crate.name);
+            writeln!(w, "{header}\n{}", "=".repeat(header.len()))?;
+            for leaked_type in &leaking_crate.types {
+                if args.color.active() {
+                    writeln!(w, "{}", Color::Blue.paint(&leaked_type.path))?;
                 } else {
-                    writeln!(w, "+{}", item)
+                    writeln!(w, "{}", leaked_type.path)?;
                 }
+                for used_by in &leaked_type.used_by {
+                    writeln!(w, "    used by {used_by}")?;
+                }
+            }
+            writeln!(w)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn print_feature_matrix(w: &mut dyn Write, args: &Args, matrix: &ApiMatrix) -> Result<()> {
+        let colored = args.color.active();
+        for (item, _) in matrix.items() {
+            print_annotated_item(
+                w,
+                args,
+                colored,
+                "",
+                item,
+                feature_matrix::annotation(matrix, item),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn print_feature_matrix_diff(
+        w: &mut dyn Write,
+        args: &Args,
+        new: &ApiMatrix,
+        diff: &FeatureMatrixDiff,
+    ) -> Result<()> {
+        let colored = args.color.active();
+        Self::print_diff(w, args, &diff.diff)?;
+
+        print_items_with_header(
+            w,
+            "Items that now require features\n\
+             ===============================",
+            &diff.now_require_features,
+            |w, item| {
+                print_annotated_item(
+                    w,
+                    args,
+                    colored,
+                    "",
+                    item,
+                    feature_matrix::annotation(new, item),
+                )
+            },
+        )?;
+
+        print_items_with_header(
+            w,
+            "Items no longer enabled by default\n\
+             ==================================",
+            &diff.no_longer_default,
+            |w, item| {
+                print_annotated_item(
+                    w,
+                    args,
+                    colored,
+                    "",
+                    item,
+                    feature_matrix::annotation(new, item),
+                )
             },
         )?;
 
         Ok(())
     }
+
+    pub fn print_target_matrix(
+        w: &mut dyn Write,
+        args: &Args,
+        matrix: &ApiMatrix,
+        targets: &[String],
+    ) -> Result<()> {
+        let colored = args.color.active();
+        for (item, item_targets) in matrix.items() {
+            let annotation = target_matrix::annotation(item_targets, targets);
+            print_annotated_item(w, args, colored, "", item, annotation)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn print_target_matrix_diff(
+        w: &mut dyn Write,
+        args: &Args,
+        diff: &TargetMatrixDiff,
+    ) -> Result<()> {
+        let colored = args.color.active();
+        let annotation = |item_targets| target_matrix::annotation(item_targets, &diff.targets);
+
+        print_items_with_header(
+            w,
+            "Removed items from the public API\n\
+             =================================",
+            &diff.removed.iter().collect::<Vec<_>>(),
+            |w, (item, item_targets)| {
+                print_annotated_item(w, args, colored, "-", item, annotation(item_targets))
+            },
+        )?;
+
+        print_items_with_header(
+            w,
+            "Changed items in the public API\n\
+             ===============================",
+            &diff.changed.iter().collect::<Vec<_>>(),
+            |w, (changed_item, item_targets)| {
+                print_changed_item(w, args, colored, changed_item, annotation(item_targets))
+            },
+        )?;
+
+        print_items_with_header(
+            w,
+            "Added items to the public API\n\
+             =============================",
+            &diff.added.iter().collect::<Vec<_>>(),
+            |w, (item, item_targets)| {
+                print_annotated_item(w, args, colored, "+", item, annotation(item_targets))
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+/// Prints `item` with a `prefix` such as `-` or `+`, followed by an
+/// `annotation` comment, if any, such as `pub fn foo() // features: bar`.
+fn print_annotated_item(
+    w: &mut dyn Write,
+    args: &Args,
+    colored: bool,
+    prefix: &str,
+    item: &PublicItem,
+    annotation: Option<String>,
+) -> Result<()> {
+    let annotation = format_annotation(args, colored, Some(item), annotation);
+    if colored {
+        writeln!(w, "{prefix}{}{annotation}", color_item(item))
+    } else {
+        writeln!(w, "{prefix}{item}{annotation}")
+    }
+}
+
+/// Prints the old and the new version of `changed_item`, with the
+/// `annotation`, if any, after the new version. With colors, the tokens that
+/// differ are highlighted.
+fn print_changed_item(
+    w: &mut dyn Write,
+    args: &Args,
+    colored: bool,
+    changed_item: &ChangedPublicItem,
+    annotation: Option<String>,
+) -> Result<()> {
+    let annotation = format_annotation(args, colored, Some(&changed_item.new), annotation);
+    if colored {
+        let old_tokens: Vec<&Token> = changed_item.old.tokens().collect();
+        let new_tokens: Vec<&Token> = changed_item.new.tokens().collect();
+        let diff_slice = diff::slice(old_tokens.as_slice(), new_tokens.as_slice());
+        writeln!(
+            w,
+            "-{}\n+{}{annotation}",
+            color_item_with_diff(&diff_slice, true),
+            color_item_with_diff(&diff_slice, false),
+        )
+    } else {
+        writeln!(
+            w,
+            "-{}\n+{}{annotation}",
+            changed_item.old, changed_item.new
+        )
+    }
+}
+
+/// Turns an annotation into a ` // annotation` comment, or an empty string if
+/// there is no annotation. With `--show-locations`, the location of `item`, if
+/// any, is put first, e.g. ` // src/lib.rs:42; features: bar`.
+fn format_annotation(
+    args: &Args,
+    colored: bool,
+    item: Option<&PublicItem>,
+    annotation: Option<String>,
+) -> String {
+    let location = item
+        .and_then(PublicItem::span)
+        .filter(|_| args.show_locations);
+    let annotation = match (location, annotation) {
+        (Some(location), Some(annotation)) => Some(format!("{location}; {annotation}")),
+        (Some(location), None) => Some(location.to_string()),
+        (None, annotation) => annotation,
+    };
+
+    match annotation {
+        Some(annotation) if colored => Style::new()
+            .dimmed()
+            .paint(format!(" // {annotation}"))
+            .to_string(),
+        Some(annotation) => format!(" // {annotation}"),
+        None => String::new(),
+    }
+}
+
+/// Prints the children of `root` as trees, e.g.
+///
+/// ```text
+/// pub mod my_crate // 3 items
+/// ├── pub fn f()
+/// └── pub struct S // 1 item
+///     └── pub fn g(&self)
+/// ```
+///
+/// Nodes with children are annotated with how many `noun`s are below them.
+fn print_tree(w: &mut dyn Write, args: &Args, root: &Node<Entry>, noun: &str) -> Result<()> {
+    let colored = args.color.active();
+    for node in root.children.values() {
+        print_tree_node(w, args, colored, node, noun, "", "")?;
+    }
+
+    Ok(())
+}
+
+/// Prints the entries of `node`, or its name if it has no entries, followed by
+/// its children. The first line is prefixed with `first_prefix`, such as
+/// `│   ├── `, and other lines with `prefix`, such as `│   │   `.
+fn print_tree_node(
+    w: &mut dyn Write,
+    args: &Args,
+    colored: bool,
+    node: &Node<Entry>,
+    noun: &str,
+    first_prefix: &str,
+    prefix: &str,
+) -> Result<()> {
+    let below = node.len() - node.entries.len();
+    // Only the first line of the node is annotated with the count
+    let mut count = (!node.children.is_empty())
+        .then(|| format!("{below} {noun}{}", if below == 1 { "" } else { "s" }));
+
+    if node.entries.is_empty() {
+        let name = node.path.last().map_or("", String::as_str);
+        let annotation = format_annotation(args, colored, None, count.take());
+        writeln!(w, "{first_prefix}{name}{annotation}")?;
+    }
+    for (index, entry) in node.entries.iter().enumerate() {
+        let entry_prefix = if index == 0 { first_prefix } else { prefix };
+        print_tree_entry(w, args, colored, entry, count.take(), entry_prefix, prefix)?;
+    }
+
+    let mut children = node.children.values().peekable();
+    while let Some(child) = children.next() {
+        let (child_first_prefix, child_prefix) = if children.peek().is_some() {
+            ("├── ", "│   ")
+        } else {
+            ("└── ", "    ")
+        };
+        print_tree_node(
+            w,
+            args,
+            colored,
+            child,
+            noun,
+            &format!("{prefix}{child_first_prefix}"),
+            &format!("{prefix}{child_prefix}"),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Prints `entry` relative to its parent, with a `-`, `~` or `+` marker if it
+/// is part of a diff. The old version of a changed item is printed on an extra
+/// line.
+fn print_tree_entry(
+    w: &mut dyn Write,
+    args: &Args,
+    colored: bool,
+    entry: &Entry,
+    annotation: Option<String>,
+    first_prefix: &str,
+    prefix: &str,
+) -> Result<()> {
+    let marker = |text: &'static str, color: Color| {
+        if colored {
+            color.paint(text).to_string()
+        } else {
+            text.to_owned()
+        }
+    };
+    let relative = |item| {
+        let tokens = tree::tokens_relative_to_parent(item);
+        if colored {
+            color_token_stream(tokens.into_iter(), None)
+        } else {
+            tokens.into_iter().map(Token::text).collect()
+        }
+    };
+
+    match entry {
+        Entry::Item(item) => {
+            let annotation = format_annotation(args, colored, Some(item), annotation);
+            writeln!(w, "{first_prefix}{}{annotation}", relative(item))
+        }
+        Entry::Removed(item) => {
+            let annotation = format_annotation(args, colored, Some(item), annotation);
+            let marker = marker("-", Color::Red);
+            writeln!(w, "{first_prefix}{marker} {}{annotation}", relative(item))
+        }
+        Entry::Added(item) => {
+            let annotation = format_annotation(args, colored, Some(item), annotation);
+            let marker = marker("+", Color::Green);
+            writeln!(w, "{first_prefix}{marker} {}{annotation}", relative(item))
+        }
+        Entry::Changed(changed_item) => {
+            let annotation = format_annotation(args, colored, Some(&changed_item.new), annotation);
+            let marker = marker("~", Color::Yellow);
+            let (old, new) = if colored {
+                let old_tokens = tree::tokens_relative_to_parent(&changed_item.old);
+                let new_tokens = tree::tokens_relative_to_parent(&changed_item.new);
+                let diff_slice = diff::slice(old_tokens.as_slice(), new_tokens.as_slice());
+                (
+                    color_item_with_diff(&diff_slice, true),
+                    color_item_with_diff(&diff_slice, false),
+                )
+            } else {
+                (relative(&changed_item.old), relative(&changed_item.new))
+            };
+            writeln!(w, "{first_prefix}{marker} {new}{annotation}")?;
+            writeln!(w, "{prefix}  was: {old}")
+        }
+    }
 }
 
 fn color_item(item: &public_api::PublicItem) -> String {
@@ -91,49 +485,53 @@ fn color_token_stream<'a>(tokens: impl Iterator<Item = &'a Token>, bg: Option<Co
 /// terminal to always provide a readable and consistent color scheme.
 /// An extra color can be provided to be used as background color.
 fn color_item_token(token: &Token, bg: Option<Color>) -> AnsiString<'_> {
-    let style = |colour: Style, text: &str| {
-        if let Some(bg) = bg {
-            colour.on(bg).paint(text.to_string())
-        } else {
-            colour.paint(text.to_string())
-        }
-    };
+    let style = token_color(token).map_or_else(Style::default, Style::from);
+    if let Some(bg) = bg {
+        style.on(bg).paint(token.text())
+    } else {
+        style.paint(token.text())
+    }
+}
+
+/// The color of a token in the syntax highlighting theme of
+/// [`color_item_token`], or `None` for the default color.
+pub fn token_color(token: &Token) -> Option<Color> {
     #[allow(clippy::match_same_arms)]
     match token {
-        Token::Symbol(text) => Paint::default(text),
-        Token::Qualifier(text) => Paint::blue(text),
-        Token::Kind(text) => Paint::blue(text),
-        Token::Whitespace => Paint::new(" "),
-        Token::Identifier(text) => Paint::cyan(text),
-        Token::Annotation(text) => Paint::default(text),
-        Token::Self_(text) => Paint::blue(text),
-        Token::Function(text) => Paint::yellow(text),
-        Token::Lifetime(text) => Paint::blue(text),
-        Token::Keyword(text) => Paint::blue(text),
-        Token::Generic(text) => Paint::green(text),
-        Token::Primitive(text) => Paint::green(text),
-        Token::Type(text) => Paint::green(text),
+        Token::Symbol(_) => None,
+        Token::Qualifier(_) => Some(Color::Blue),
+        Token::Kind(_) => Some(Color::Blue),
+        Token::Whitespace => None,
+        Token::Identifier(_) => Some(Color::Cyan),
+        Token::Annotation(_) => None,
+        Token::Self_(_) => Some(Color::Blue),
+        Token::Function(_) => Some(Color::Yellow),
+        Token::Lifetime(_) => Some(Color::Blue),
+        Token::Keyword(_) => Some(Color::Blue),
+        Token::Generic(_) => Some(Color::Green),
+        Token::Primitive(_) => Some(Color::Green),
+        Token::Type(_) => Some(Color::Green),
     }
 }
 
 /// Returns a styled string similar to `color_item_token`, but where whole tokens are highlighted if
 /// they contain a difference.
 fn color_item_with_diff(diff_slice: &[diff::Result<&&Token>], is_old_item: bool) -> String {
-    diff_slice
+    let styled_strings = diff_slice
         .iter()
         .filter_map(|diff_result| match diff_result {
             diff::Result::Left(&token) => is_old_item.then(|| {
-                Paint::new(token.text())
-                    .fg(Color::Fixed(9))
-                    .bg(Color::Fixed(52))
+                Color::Fixed(9)
+                    .on(Color::Fixed(52))
                     .bold()
+                    .paint(token.text())
             }),
             diff::Result::Both(&token, _) => Some(color_item_token(token, None)),
             diff::Result::Right(&token) => (!is_old_item).then(|| {
-                Paint::new(token.text())
-                    .fg(Color::Fixed(10))
-                    .bg(Color::Fixed(22))
+                Color::Fixed(10)
+                    .on(Color::Fixed(22))
                     .bold()
+                    .paint(token.text())
             }),
         })
         .collect::<Vec<_>>();
@@ -141,13 +539,34 @@ fn color_item_with_diff(diff_slice: &[diff::Result<&&Token>], is_old_item: bool)
     AnsiStrings(&styled_strings).to_string()
 }
 
+/// Like [`print_items_with_header`], but with a header that counts the items,
+/// and nothing at all if there are no items.
+fn print_violations_with_header<T>(
+    w: &mut dyn Write,
+    kind: &str,
+    items: &[T],
+    print_fn: impl Fn(&mut dyn Write, &T) -> Result<()>,
+) -> Result<()> {
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let header = format!("{kind} items not allowed as per --deny ({})", items.len());
+    print_items_with_header(
+        w,
+        &format!("{header}\n{}", "=".repeat(header.len())),
+        items,
+        print_fn,
+    )
+}
+
 pub fn print_items_with_header<T>(
     w: &mut dyn Write,
     header: &str,
     items: &[T],
     print_fn: impl Fn(&mut dyn Write, &T) -> Result<()>,
 ) -> Result<()> {
-    writeln!(w, "{}", header)?;
+    writeln!(w, "{header}")?;
     if items.is_empty() {
         writeln!(w, "(none)")?;
     } else {
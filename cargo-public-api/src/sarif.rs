@@ -0,0 +1,155 @@
+use std::io::{Result, Write};
+use std::path::Path;
+
+use public_api::{diff::PublicItemsDiff, PublicItem};
+use serde_json::{json, Value};
+
+use crate::arg_types::DenyMethod;
+use crate::Args;
+
+/// Prints a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+/// log, which is understood by many dashboards for static analysis results.
+pub struct Sarif;
+
+/// The rules that API changes violate, in the order they are listed in the log.
+/// Results refer to rules by their index in this list.
+const RULES: [Rule; 3] = [
+    Rule {
+        id: "removed-item",
+        name: "RemovedItem",
+        description: "An item was removed from the public API",
+    },
+    Rule {
+        id: "changed-signature",
+        name: "ChangedSignature",
+        description: "An item in the public API was changed",
+    },
+    Rule {
+        id: "added-item",
+        name: "AddedItem",
+        description: "An item was added to the public API",
+    },
+];
+const REMOVED_ITEM: usize = 0;
+const CHANGED_SIGNATURE: usize = 1;
+const ADDED_ITEM: usize = 2;
+
+struct Rule {
+    id: &'static str,
+    name: &'static str,
+    description: &'static str,
+}
+
+impl Sarif {
+    /// Prints a log with one result for each changed item. Results for changes
+    /// denied by `--deny` are errors, other results are notes. Removed items
+    /// point to the old code, other items to the new code.
+    pub fn print_diff(w: &mut dyn Write, args: &Args, diff: &PublicItemsDiff) -> Result<()> {
+        let package_dir = args.package_dir_in_git_root().unwrap_or_default();
+        let mut results = vec![];
+
+        for item in &diff.removed {
+            results.push(result(
+                REMOVED_ITEM,
+                args.denies(DenyMethod::deny_removed),
+                &package_dir,
+                item,
+                &format!("Removed {item}"),
+            ));
+        }
+
+        for changed_item in &diff.changed {
+            results.push(result(
+                CHANGED_SIGNATURE,
+                args.denies(DenyMethod::deny_changed),
+                &package_dir,
+                &changed_item.new,
+                &format!("Changed\n-{}\n+{}", changed_item.old, changed_item.new),
+            ));
+        }
+
+        for item in &diff.added {
+            results.push(result(
+                ADDED_ITEM,
+                args.denies(DenyMethod::deny_added),
+                &package_dir,
+                item,
+                &format!("Added {item}"),
+            ));
+        }
+
+        let rules: Vec<Value> = RULES
+            .iter()
+            .map(|rule| {
+                json!({
+                    "id": rule.id,
+                    "name": rule.name,
+                    "shortDescription": { "text": rule.description },
+                })
+            })
+            .collect();
+
+        let log = json!({
+            "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+            "version": "2.1.0",
+            "runs": [{
+                "tool": {
+                    "driver": {
+                        "name": env!("CARGO_PKG_NAME"),
+                        "version": env!("CARGO_PKG_VERSION"),
+                        "informationUri": env!("CARGO_PKG_HOMEPAGE"),
+                        "rules": rules,
+                    },
+                },
+                "results": results,
+            }],
+        });
+
+        serde_json::to_writer_pretty(&mut *w, &log)?;
+        writeln!(w)
+    }
+}
+
+/// A result for `item` that violates the rule at `rule_index` in [`RULES`].
+/// The path of the item is its logical location, and its span, if any, its
+/// physical location.
+fn result(
+    rule_index: usize,
+    denied: bool,
+    package_dir: &Path,
+    item: &PublicItem,
+    message: &str,
+) -> Value {
+    let mut location = json!({
+        "logicalLocations": [{
+            "name": item.path().last(),
+            "fullyQualifiedName": item.path().join("::"),
+        }],
+    });
+    if let Some(span) = item.span() {
+        // SARIF wants forward slashes, also on Windows
+        let uri = package_dir
+            .join(span.filename())
+            .to_string_lossy()
+            .replace('\\', "/");
+        // Lines start at 1 in both rustdoc JSON and SARIF, but columns start
+        // at 0 in rustdoc JSON and at 1 in SARIF
+        location["physicalLocation"] = json!({
+            "artifactLocation": { "uri": uri, "uriBaseId": "%SRCROOT%" },
+            "region": {
+                "startLine": span.begin().0,
+                "startColumn": span.begin().1 + 1,
+                "endLine": span.end().0,
+                "endColumn": span.end().1 + 1,
+            },
+        });
+    }
+
+    json!({
+        "ruleId": RULES[rule_index].id,
+        "ruleIndex": rule_index,
+        "level": if denied { "error" } else { "note" },
+        "message": { "text": message },
+        "locations": [location],
+    })
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use public_api::{
+    diff::{ChangedPublicItem, PublicItemsDiff},
+    PublicItem,
+};
+
+use crate::api_matrix::ApiMatrix;
+use crate::{build_options, collect_public_api, Args};
+
+/// Builds rustdoc JSON once for each target triple given with `--target` and
+/// collects the public API of each.
+pub fn collect(args: &Args) -> Result<ApiMatrix> {
+    let mut matrix = ApiMatrix::new();
+    for target in &args.target {
+        let build_options = build_options(args).target(target.clone());
+        matrix.add(target, collect_public_api(args, build_options)?.items);
+    }
+    Ok(matrix)
+}
+
+/// Describes what targets an item exists on, e.g. `targets: wasm32-unknown-unknown`.
+/// Returns `None` if the item exists on all `targets`.
+pub fn annotation(item_targets: &[String], targets: &[String]) -> Option<String> {
+    if targets.iter().all(|target| item_targets.contains(target)) {
+        None
+    } else {
+        let item_targets = targets
+            .iter()
+            .filter(|target| item_targets.contains(target))
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        Some(format!("targets: {}", item_targets.join(", ")))
+    }
+}
+
+/// The difference between the public APIs of two [`ApiMatrix`]es built by
+/// [`collect()`]. The public API of each target is diffed separately, and the
+/// result is merged so that each diffed item maps to the targets it was
+/// removed, changed, or added on.
+pub struct TargetMatrixDiff {
+    /// All targets that were diffed, in the order they were given.
+    pub targets: Vec<String>,
+
+    /// Items removed from the public API of at least one target.
+    pub removed: BTreeMap<PublicItem, Vec<String>>,
+
+    /// Items changed in the public API of at least one target.
+    pub changed: BTreeMap<ChangedPublicItem, Vec<String>>,
+
+    /// Items added to the public API of at least one target.
+    pub added: BTreeMap<PublicItem, Vec<String>>,
+}
+
+impl TargetMatrixDiff {
+    pub fn between(old: &ApiMatrix, new: &ApiMatrix, targets: &[String]) -> Self {
+        let mut removed = BTreeMap::<_, Vec<_>>::new();
+        let mut changed = BTreeMap::<_, Vec<_>>::new();
+        let mut added = BTreeMap::<_, Vec<_>>::new();
+        for target in targets {
+            let diff = PublicItemsDiff::between(old.items_of(target), new.items_of(target));
+            for item in diff.removed {
+                removed.entry(item).or_default().push(target.clone());
+            }
+            for item in diff.changed {
+                changed.entry(item).or_default().push(target.clone());
+            }
+            for item in diff.added {
+                added.entry(item).or_default().push(target.clone());
+            }
+        }
+
+        Self {
+            targets: targets.to_vec(),
+            removed,
+            changed,
+            added,
+        }
+    }
+
+    /// The diff to check with `--deny`. An item counts as e.g. removed if it
+    /// was removed on any target.
+    pub fn diff_to_check(&self) -> PublicItemsDiff {
+        PublicItemsDiff {
+            removed: self.removed.keys().cloned().collect(),
+            changed: self.changed.keys().cloned().collect(),
+            added: self.added.keys().cloned().collect(),
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use public_api::{
+    diff::{ChangedPublicItem, PublicItemsDiff},
+    tokens::Token,
+    PublicItem,
+};
+
+/// Items grouped by their paths. Each component of a path is a node, so that
+/// e.g. `pub fn my_crate::a::Struct::f` is an entry of the `f` node, which is a
+/// child of the `Struct` node, which in turn is a child of the `a` node.
+pub struct Node<T> {
+    /// The path of the node, e.g. `["my_crate", "a", "Struct"]`.
+    pub path: Vec<String>,
+
+    /// The entries whose path ends at this node. There can be several, e.g.
+    /// the `fmt` methods of both `Debug` and `Display`.
+    pub entries: Vec<T>,
+
+    /// Nodes whose path has one more component than this node, sorted by name.
+    pub children: BTreeMap<String, Node<T>>,
+}
+
+/// An item in a listing or a diff of the public API.
+pub enum Entry<'a> {
+    Item(&'a PublicItem),
+    Removed(&'a PublicItem),
+    Changed(&'a ChangedPublicItem),
+    Added(&'a PublicItem),
+}
+
+impl<T> Node<T> {
+    /// Groups `entries` by the paths given along with them. Returns a root
+    /// node with an empty path, whose children are the crate root modules.
+    pub fn from_entries<'p>(entries: impl IntoIterator<Item = (&'p [String], T)>) -> Self {
+        let mut root = Node::new(vec![]);
+        for (path, entry) in entries {
+            let mut node = &mut root;
+            for (depth, name) in path.iter().enumerate() {
+                node = node
+                    .children
+                    .entry(name.clone())
+                    .or_insert_with(|| Node::new(path[..=depth].to_vec()));
+            }
+            node.entries.push(entry);
+        }
+
+        root
+    }
+
+    fn new(path: Vec<String>) -> Self {
+        Self {
+            path,
+            entries: vec![],
+            children: BTreeMap::new(),
+        }
+    }
+
+    /// The number of entries in this node and all nodes below it.
+    pub fn len(&self) -> usize {
+        self.entries.len() + self.children.values().map(Node::len).sum::<usize>()
+    }
+}
+
+impl<'a> Node<Entry<'a>> {
+    /// Groups a listing of the public API.
+    pub fn from_items(items: &'a [PublicItem]) -> Self {
+        Node::from_entries(items.iter().map(|item| (item.path(), Entry::Item(item))))
+    }
+
+    /// Groups a diff of the public API. Changed items are grouped by their new
+    /// path.
+    pub fn from_diff(diff: &'a PublicItemsDiff) -> Self {
+        let removed = diff
+            .removed
+            .iter()
+            .map(|item| (item.path(), Entry::Removed(item)));
+        let changed = diff
+            .changed
+            .iter()
+            .map(|changed_item| (changed_item.new.path(), Entry::Changed(changed_item)));
+        let added = diff
+            .added
+            .iter()
+            .map(|item| (item.path(), Entry::Added(item)));
+
+        Node::from_entries(removed.chain(changed).chain(added))
+    }
+}
+
+/// The tokens of `item` without the path of its parent, so that e.g.
+/// `pub fn my_crate::a::f()` becomes `pub fn f()`. If the path can not be found
+/// among the tokens, all tokens are returned.
+pub fn tokens_relative_to_parent(item: &PublicItem) -> Vec<&Token> {
+    let tokens: Vec<&Token> = item.tokens().collect();
+    let path = item.path();
+    if path.len() < 2 {
+        return tokens;
+    }
+
+    // The path is rendered as e.g. `my_crate`, `::`, `a`, `::`, `f`
+    let path_len = path.len() * 2 - 1;
+    let is_path_at = |start: usize| {
+        (0..path_len).all(|i| {
+            let expected = if i % 2 == 0 {
+                path[i / 2].as_str()
+            } else {
+                "::"
+            };
+            tokens[start + i].text() == expected
+        })
+    };
+    match (0..(tokens.len() + 1).saturating_sub(path_len)).find(|&start| is_path_at(start)) {
+        Some(start) => {
+            let parent_len = (path.len() - 1) * 2;
+            let mut relative = tokens;
+            relative.drain(start..start + parent_len);
+            relative
+        }
+        None => tokens,
+    }
+}
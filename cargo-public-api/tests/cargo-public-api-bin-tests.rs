@@ -15,6 +15,7 @@ use std::{
 
 use assert_cmd::assert::Assert;
 use assert_cmd::Command;
+use predicates::prelude::PredicateBooleanExt;
 use predicates::str::contains;
 
 // rust-analyzer bug: https://github.com/rust-lang/rust-analyzer/issues/9173
@@ -115,6 +116,65 @@ fn target_arg() {
         .success();
 }
 
+#[test]
+fn target_arg_ignored_for_proc_macro_crate() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args([
+        "--manifest-path",
+        "../test-apis/comprehensive_api_proc_macro/Cargo.toml",
+    ]);
+    cmd.args(["--target", "wasm32-unknown-unknown"]);
+    cmd.assert()
+        .stdout(include_str!(
+            "../../public-api/tests/expected-output/comprehensive_api_proc_macro.txt"
+        ))
+        .stderr(contains("ignoring `--target`"))
+        .success();
+}
+
+#[test]
+fn leaked_types() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args([
+        "--manifest-path",
+        "../test-apis/comprehensive_api/Cargo.toml",
+        "--leaked-types",
+    ]);
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/comprehensive_api_leaked_types.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn deny_leaked_types() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args([
+        "--manifest-path",
+        "../test-apis/comprehensive_api/Cargo.toml",
+        "--leaked-types",
+        "--deny-leaked-types",
+    ]);
+    cmd.assert()
+        .stderr(contains(
+            "not allowed as per --deny-leaked-types: example_api",
+        ))
+        .failure();
+}
+
+#[test]
+fn deny_leaked_types_with_allowlist() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args([
+        "--manifest-path",
+        "../test-apis/comprehensive_api/Cargo.toml",
+        "--leaked-types",
+        "--deny-leaked-types=other_crate,example_api",
+    ]);
+    cmd.assert().success();
+}
+
 #[test]
 fn virtual_manifest_error() {
     let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
@@ -128,6 +188,31 @@ fn virtual_manifest_error() {
         .failure();
 }
 
+#[test]
+fn list_public_items_with_custom_lib_name_via_package_spec() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.arg("--manifest-path");
+    cmd.arg(current_dir_and("tests/virtual-manifest/Cargo.toml"));
+    cmd.arg("--package");
+    cmd.arg("custom-lib-name");
+    cmd.assert()
+        .stdout("pub fn renamed_lib::in_renamed_lib()\npub mod renamed_lib\n")
+        .success();
+}
+
+#[test]
+fn bin_only_package_error() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.arg("--manifest-path");
+    cmd.arg(current_dir_and("tests/virtual-manifest/Cargo.toml"));
+    cmd.arg("--package");
+    cmd.arg("bin-only");
+    cmd.assert()
+        .stdout("")
+        .stderr(contains("Package `bin-only` has no lib target"))
+        .failure();
+}
+
 #[test]
 fn diff_public_items() {
     let mut cmd = TestCmd::new();
@@ -187,11 +272,7 @@ fn diff_public_items_with_dirty_tree_fails() {
     let mut lib_rs_path = test_repo.path.path().to_owned();
     lib_rs_path.push("src/lib.rs");
 
-    let mut lib_rs = OpenOptions::new()
-        .write(true)
-        .append(true)
-        .open(&lib_rs_path)
-        .unwrap();
+    let mut lib_rs = OpenOptions::new().append(true).open(&lib_rs_path).unwrap();
 
     writeln!(lib_rs, "// Make git tree dirty").unwrap();
 
@@ -209,6 +290,145 @@ fn diff_public_items_with_dirty_tree_fails() {
         .failure();
 }
 
+#[test]
+fn diff_public_items_with_github_output_format() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=github");
+    cmd.arg("--deny=changed");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0_github.txt"
+        ))
+        .stderr(contains("The API diff is not allowed as per --deny"))
+        .failure();
+}
+
+#[test]
+fn diff_public_items_with_github_output_format_without_deny() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=github");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.assert()
+        .stdout(contains(
+            "::notice file=src/lib.rs,line=16,endLine=16::Changed%0A-pub fn example_api::function(v1_param: Struct)",
+        ))
+        .stdout(contains("Breaking change").not())
+        .success();
+}
+
+#[test]
+fn diff_public_items_with_sarif_output_format() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=sarif");
+    cmd.arg("--deny=changed");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    let assert = cmd.assert().failure();
+
+    let log: serde_json::Value = serde_json::from_slice(&assert.get_output().stdout).unwrap();
+    assert_valid_sarif(&log);
+
+    let results = log["runs"][0]["results"].as_array().unwrap();
+    let result_summaries: Vec<_> = results
+        .iter()
+        .map(|result| {
+            let location = &result["locations"][0];
+            format!(
+                "{} {} {} {}:{}",
+                result["level"].as_str().unwrap(),
+                result["ruleId"].as_str().unwrap(),
+                location["logicalLocations"][0]["fullyQualifiedName"]
+                    .as_str()
+                    .unwrap(),
+                location["physicalLocation"]["artifactLocation"]["uri"]
+                    .as_str()
+                    .unwrap(),
+                location["physicalLocation"]["region"]["startLine"],
+            )
+        })
+        .collect();
+    assert_eq!(
+        result_summaries,
+        [
+            "error changed-signature example_api::function src/lib.rs:16",
+            "error changed-signature example_api::Struct src/lib.rs:7",
+            "note added-item example_api::StructV2 src/lib.rs:12",
+            "note added-item example_api::Struct::v2_field src/lib.rs:9",
+            "note added-item example_api::StructV2::field src/lib.rs:13",
+        ]
+    );
+}
+
+#[test]
+fn diff_public_items_with_junit_output_format() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=junit");
+    cmd.arg("--deny=changed");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0_junit.xml"
+        ))
+        .stderr(contains("The API diff is not allowed as per --deny"))
+        .failure();
+}
+
+#[test]
+fn list_public_items_with_html_output_format() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=html");
+    cmd.assert()
+        .stdout(include_str!("./expected-output/example_api_v0.3.0.html"))
+        .success();
+}
+
+#[test]
+fn diff_public_items_with_html_output_format() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=html");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0.html"
+        ))
+        .success();
+}
+
+#[test]
+fn html_output_format_with_feature_matrix() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=html");
+    cmd.arg("--feature-matrix");
+    cmd.assert()
+        .stdout("")
+        .stderr(contains(
+            "`--output-format html` can not be used with `--feature-matrix`",
+        ))
+        .failure();
+}
+
+#[test]
+fn github_output_format_when_not_diffing() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--output-format=github");
+    cmd.assert()
+        .stdout("")
+        .stderr(contains(
+            "`--output-format github` can only be used when diffing",
+        ))
+        .failure();
+}
+
 #[test]
 fn deny_when_not_diffing() {
     let mut cmd = TestCmd::new();
@@ -299,7 +519,62 @@ fn deny_changed_with_diff() {
     cmd.arg("v0.1.0");
     cmd.arg("v0.2.0");
     cmd.arg("--deny=changed");
-    cmd.assert().failure();
+    cmd.arg("--output-format=github");
+    cmd.assert()
+        .stderr(contains(
+            "Changed items not allowed as per --deny (2)\n\
+             ===========================================\n\
+             -pub fn example_api::function(v1_param: Struct)\n\
+             +pub fn example_api::function(v1_param: Struct, v2_param: usize)\n\
+             -pub struct example_api::Struct\n\
+             +#[non_exhaustive] pub struct example_api::Struct\n",
+        ))
+        .stderr(contains("Added items not allowed").not())
+        .stderr(contains(
+            "The API diff is not allowed as per --deny: 2 changed items",
+        ))
+        .failure();
+}
+
+/// The plain diff on stdout already lists the denied items, so they must not
+/// be listed on stderr as well
+#[test]
+fn deny_changed_with_plain_diff_only_prints_hint() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.arg("--deny=changed");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0.txt"
+        ))
+        .stderr(contains("Changed items not allowed").not())
+        .stderr(contains("docs/CI-EXAMPLES.md#with-a-changeable-public-api"))
+        .stderr(contains(
+            "The API diff is not allowed as per --deny: 2 changed items",
+        ))
+        .failure();
+}
+
+#[test]
+fn deny_all_and_added_with_diff_lists_items_once() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.arg("--deny=all");
+    cmd.arg("--deny=added");
+    cmd.arg("--output-format=github");
+    cmd.assert()
+        .stderr(contains("Added items not allowed as per --deny (3)"))
+        .stderr(contains(
+            "dropping or narrowing `--deny=all` and `--deny=added`",
+        ))
+        .stderr(contains(
+            "The API diff is not allowed as per --deny: 2 changed items, 3 added items",
+        ))
+        .failure();
 }
 
 #[test]
@@ -309,9 +584,17 @@ fn deny_removed_with_diff() {
     cmd.arg("v0.2.0");
     cmd.arg("v0.3.0");
     cmd.arg("--deny=removed");
+    cmd.arg("--output-format=github");
     cmd.assert()
         .stderr(contains(
-            "The API diff is not allowed as per --deny: Removed items not allowed: [pub fn example_api::function(v1_param: Struct, v2_param: usize)]",
+            "Removed items not allowed as per --deny (1)\n\
+             ===========================================\n\
+             -pub fn example_api::function(v1_param: Struct, v2_param: usize)\n\
+             \n\
+             Hint: If the changes are intended, allow them by dropping or narrowing `--deny=removed`",
+        ))
+        .stderr(contains(
+            "The API diff is not allowed as per --deny: 1 removed item",
         ))
         .failure();
 }
@@ -391,6 +674,69 @@ fn list_public_items_with_color() {
         .success();
 }
 
+#[test]
+fn list_public_items_with_locations() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--show-locations");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_v0.3.0_locations.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn diff_public_items_with_locations() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--show-locations");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0_locations.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn list_public_items_as_tree() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--tree");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_v0.3.0_tree.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn diff_public_items_as_tree() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--tree");
+    cmd.arg("--diff-git-checkouts");
+    cmd.arg("v0.1.0");
+    cmd.arg("v0.2.0");
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/example_api_diff_v0.1.0_to_v0.2.0_tree.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn tree_with_html_output_format() {
+    let mut cmd = TestCmd::new();
+    cmd.arg("--tree");
+    cmd.arg("--output-format=html");
+    cmd.assert()
+        .stdout("")
+        .stderr(contains(
+            "`--tree` can not be used with `--output-format html`",
+        ))
+        .failure();
+}
+
 #[test]
 fn diff_public_items_from_files() {
     let old = rustdoc_json_path_for_crate("../test-apis/example_api-v0.1.0");
@@ -425,8 +771,10 @@ fn verbose() {
     cmd.arg("../test-apis/lint_error/Cargo.toml");
     cmd.arg("--verbose");
     cmd.assert()
-        .stdout(contains("Processing \""))
-        .stdout(contains("rustdoc JSON missing referenced item"))
+        .stdout(contains("Processing ").and(contains("lint_error.json")))
+        .stdout(contains(
+            "NOTE: `lint_error::example_api` is missing from the rustdoc JSON",
+        ))
         .success();
 }
 
@@ -452,6 +800,20 @@ fn assert_presence_of_args_in_help(mut cmd: Command) {
         .success();
 }
 
+/// Validates `log` against the SARIF 2.1.0 JSON schema
+fn assert_valid_sarif(log: &serde_json::Value) {
+    let schema: serde_json::Value =
+        serde_json::from_str(include_str!("./sarif-schema-2.1.0.json")).unwrap();
+    let schema = jsonschema::JSONSchema::compile(&schema).unwrap();
+    let errors: Vec<String> = match schema.validate(log) {
+        Ok(()) => vec![],
+        Err(errors) => errors
+            .map(|e| format!("{e} at {}", e.instance_path))
+            .collect(),
+    };
+    assert!(errors.is_empty(), "Invalid SARIF: {errors:#?}");
+}
+
 /// Helper to get the absolute path to a given path, relative to the current
 /// path
 fn current_dir_and<P: AsRef<Path>>(path: P) -> PathBuf {
@@ -555,6 +917,146 @@ fn cargo_public_api_with_features() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn feature_matrix() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args(["--manifest-path", "../test-apis/features/Cargo.toml"]);
+    cmd.arg("--feature-matrix");
+    cmd.assert()
+        .stdout(include_str!("./expected-output/features-matrix.txt"))
+        .success();
+}
+
+#[test]
+fn feature_matrix_diff() {
+    let test_repo = feature_matrix_test_repo();
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.current_dir(test_repo.path());
+    cmd.arg("--color=never");
+    cmd.arg("--feature-matrix");
+    cmd.args(["--diff-git-checkouts", "v0.1.0", "v0.2.0"]);
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/feature_matrix_diff_v0.1.0_to_v0.2.0.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn feature_matrix_diff_deny_removed() {
+    let test_repo = feature_matrix_test_repo();
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.current_dir(test_repo.path());
+    cmd.arg("--feature-matrix");
+    cmd.args(["--diff-git-checkouts", "v0.1.0", "v0.2.0"]);
+    cmd.arg("--deny=removed");
+    cmd.assert()
+        .stdout(contains("becomes_feature_gated"))
+        .stderr(contains("Hint: If the changes are intended"))
+        .failure();
+}
+
+#[test]
+fn feature_matrix_with_features() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args(["--manifest-path", "../test-apis/features/Cargo.toml"]);
+    cmd.args(["--feature-matrix", "--features", "feature_a"]);
+    cmd.assert()
+        .stderr(contains("cannot be used with"))
+        .failure();
+}
+
+#[test]
+fn target_matrix() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args([
+        "--manifest-path",
+        "../test-apis/target_matrix-v0.2.0/Cargo.toml",
+    ]);
+    cmd.args(TARGET_MATRIX_TARGETS);
+    cmd.assert()
+        .stdout(include_str!("./expected-output/target_matrix.txt"))
+        .success();
+}
+
+#[test]
+fn target_matrix_diff() {
+    let test_repo = target_matrix_test_repo();
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.current_dir(test_repo.path());
+    cmd.arg("--color=never");
+    cmd.args(TARGET_MATRIX_TARGETS);
+    cmd.args(["--diff-git-checkouts", "v0.1.0", "v0.2.0"]);
+    cmd.assert()
+        .stdout(include_str!(
+            "./expected-output/target_matrix_diff_v0.1.0_to_v0.2.0.txt"
+        ))
+        .success();
+}
+
+#[test]
+fn target_matrix_diff_deny_changed() {
+    let test_repo = target_matrix_test_repo();
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.current_dir(test_repo.path());
+    cmd.args(TARGET_MATRIX_TARGETS);
+    cmd.args(["--diff-git-checkouts", "v0.1.0", "v0.2.0"]);
+    cmd.arg("--deny=changed");
+    cmd.assert()
+        .stdout(contains("changed_on_wasm"))
+        .stderr(contains("Hint: If the changes are intended"))
+        .failure();
+}
+
+#[test]
+fn target_matrix_with_feature_matrix() {
+    let mut cmd = Command::cargo_bin("cargo-public-api").unwrap();
+    cmd.args([
+        "--manifest-path",
+        "../test-apis/target_matrix-v0.2.0/Cargo.toml",
+    ]);
+    cmd.args(TARGET_MATRIX_TARGETS);
+    cmd.arg("--feature-matrix");
+    cmd.assert()
+        .stderr(contains("can not be combined with `--feature-matrix`"))
+        .failure();
+}
+
+/// Helper to create a git repo with two versions of the `feature_matrix` test
+/// API, tagged `v0.1.0` and `v0.2.0`.
+fn feature_matrix_test_repo() -> tempfile::TempDir {
+    let tempdir = tempfile::tempdir().unwrap();
+    test_utils::create_test_git_repo_for_versions_of(
+        tempdir.path(),
+        "../test-apis",
+        "feature_matrix",
+        &["v0.1.0", "v0.2.0"],
+    );
+    tempdir
+}
+
+/// The targets used to test several `--target`s. Both need to be installed,
+/// e.g. with `rustup target add`.
+const TARGET_MATRIX_TARGETS: [&str; 4] = [
+    "--target",
+    "x86_64-unknown-linux-gnu",
+    "--target",
+    "wasm32-unknown-unknown",
+];
+
+/// Helper to create a git repo with two versions of the `target_matrix` test
+/// API, tagged `v0.1.0` and `v0.2.0`.
+fn target_matrix_test_repo() -> tempfile::TempDir {
+    let tempdir = tempfile::tempdir().unwrap();
+    test_utils::create_test_git_repo_for_versions_of(
+        tempdir.path(),
+        "../test-apis",
+        "target_matrix",
+        &["v0.1.0", "v0.2.0"],
+    );
+    tempdir
+}
+
 /// A git repository that lives during the duration of a test. Having each test
 /// have its own git repository to test with makes tests runnable concurrently.
 struct TestRepo {
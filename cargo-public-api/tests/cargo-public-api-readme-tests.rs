@@ -7,6 +7,6 @@ use public_api::MINIMUM_RUSTDOC_JSON_VERSION;
 fn installation_instructions_mentions_minimum_rustdoc_json_version() {
     let readme = include_str!("../../README.md");
     let expected_installation_instruction =
-        format!("# Ensure {} or later", MINIMUM_RUSTDOC_JSON_VERSION);
+        format!("# Ensure {MINIMUM_RUSTDOC_JSON_VERSION} or later");
     assert!(readme.contains(&expected_installation_instruction));
 }
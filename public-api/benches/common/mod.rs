@@ -0,0 +1,136 @@
+//! Generates rustdoc JSON for large synthetic crates, since the rustdoc JSON of
+//! crates such as `windows` is too big to check in.
+
+use std::io::Write;
+
+use serde_json::json;
+
+/// How deeply modules are nested at most. Module `n` is put in module `n - 1`,
+/// except every `MODULE_DEPTH`th module which is put in the crate root.
+const MODULE_DEPTH: usize = 5;
+
+/// Writes rustdoc JSON, in the format version we process, for a crate with
+/// `modules` modules that each have `structs_per_module` documented structs
+/// with one field each.
+pub fn write_rustdoc_json(
+    mut writer: impl Write,
+    modules: usize,
+    structs_per_module: usize,
+) -> std::io::Result<()> {
+    let module_ids: Vec<String> = (0..modules).map(|m| format!("0:m{m}")).collect();
+    let top_level_module_ids: Vec<&String> = module_ids.iter().step_by(MODULE_DEPTH).collect();
+
+    write!(
+        writer,
+        r#"{{"root":"0:0","crate_version":null,"includes_private":false,"index":{{"#
+    )?;
+    write_item(
+        &mut writer,
+        "0:0",
+        "huge_crate",
+        "module",
+        &module(&top_level_module_ids),
+    )?;
+
+    for (m, module_id) in module_ids.iter().enumerate() {
+        let mut children: Vec<String> = (0..structs_per_module)
+            .map(|s| format!("0:m{m}s{s}"))
+            .collect();
+        if (m + 1) % MODULE_DEPTH != 0 {
+            children.extend(module_ids.get(m + 1).cloned());
+        }
+        write_item(
+            &mut writer,
+            module_id,
+            &format!("mod_{m}"),
+            "module",
+            &module(&children),
+        )?;
+
+        for s in 0..structs_per_module {
+            let struct_id = format!("0:m{m}s{s}");
+            let field_id = format!("{struct_id}f");
+            write_item(
+                &mut writer,
+                &struct_id,
+                &format!("Struct{s}"),
+                "struct",
+                &json!({
+                    "kind": { "plain": { "fields": [field_id], "fields_stripped": false } },
+                    "generics": { "params": [], "where_predicates": [] },
+                    "impls": [],
+                }),
+            )?;
+            write_item(
+                &mut writer,
+                &field_id,
+                "field",
+                "struct_field",
+                &json!({
+                    "kind": "primitive",
+                    "inner": "usize",
+                }),
+            )?;
+        }
+    }
+
+    write!(
+        writer,
+        r#"}},"paths":{{}},"external_crates":{{}},"format_version":{}}}"#,
+        rustdoc_types::FORMAT_VERSION
+    )
+}
+
+/// Same as [`write_rustdoc_json`], but returns the rustdoc JSON as a string.
+#[allow(dead_code)] // Not all benches use it
+pub fn rustdoc_json(modules: usize, structs_per_module: usize) -> String {
+    let mut json = vec![];
+    write_rustdoc_json(&mut json, modules, structs_per_module).unwrap();
+    String::from_utf8(json).unwrap()
+}
+
+fn module(items: &[impl serde::Serialize]) -> serde_json::Value {
+    json!({
+        "is_crate": false,
+        "items": items,
+        "is_stripped": false,
+    })
+}
+
+/// Writes an item as an entry of the `index` of the crate, i.e. with a leading
+/// comma since the root module is always written first.
+fn write_item(
+    mut writer: impl Write,
+    id: &str,
+    name: &str,
+    kind: &str,
+    inner: &serde_json::Value,
+) -> std::io::Result<()> {
+    if id != "0:0" {
+        write!(writer, ",")?;
+    }
+    write!(writer, r#""{id}":"#)?;
+    serde_json::to_writer(
+        writer,
+        &json!({
+            "id": id,
+            "crate_id": 0,
+            "name": name,
+            "span": {
+                "filename": "src/lib.rs",
+                "begin": [1, 0],
+                "end": [1, 0],
+            },
+            "visibility": "public",
+            "docs": format!("Documentation of `{name}` that is long enough to resemble real docs. \
+                Real crates document their items in much more detail than this, so this is \
+                if anything an underestimate of the size of real rustdoc JSON."),
+            "links": {},
+            "attrs": [],
+            "deprecation": null,
+            "kind": kind,
+            "inner": inner,
+        }),
+    )?;
+    Ok(())
+}
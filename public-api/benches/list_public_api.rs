@@ -0,0 +1,40 @@
+//! Measures how long it takes to list the public API of synthetic crates of
+//! different sizes. The time per item should stay about the same regardless of
+//! crate size. Run with
+//!
+//! ```bash
+//! cargo bench -p public-api --bench list_public_api
+//! ```
+
+// deny in CI, only warn here
+#![warn(clippy::all, clippy::pedantic)]
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use public_api::{public_api_from_rustdoc_json_str, Options};
+
+mod common;
+
+const STRUCTS_PER_MODULE: usize = 100;
+
+fn list_public_api(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_public_api");
+    group.sample_size(10);
+
+    for modules in [10, 100, 1_000] {
+        let json = common::rustdoc_json(modules, STRUCTS_PER_MODULE);
+        let items = public_api_from_rustdoc_json_str(&json, Options::default())
+            .unwrap()
+            .items
+            .len();
+
+        group.throughput(Throughput::Elements(items as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(items), &json, |b, json| {
+            b.iter(|| public_api_from_rustdoc_json_str(json, Options::default()).unwrap());
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, list_public_api);
+criterion_main!(benches);
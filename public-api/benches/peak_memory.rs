@@ -0,0 +1,132 @@
+//! Compares peak heap memory usage of the different ways to get the public API
+//! from a rustdoc JSON file. Run with
+//!
+//! ```bash
+//! cargo bench -p public-api --features mmap --bench peak_memory
+//! ```
+//!
+//! A large rustdoc JSON file is generated for the purpose, since the rustdoc
+//! JSON of crates such as `windows` is too big to check in. Note that
+//! memory-mapped file contents do not count as heap memory. That is the point:
+//! the operating system can page them in and out as needed.
+
+// deny in CI, only warn here
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use public_api::{
+    public_api_from_rustdoc_json_mmap, public_api_from_rustdoc_json_path,
+    public_api_from_rustdoc_json_reader, public_api_from_rustdoc_json_str, Options, PublicApi,
+};
+
+mod common;
+
+/// The size of the synthetic crate. Each struct also has a field, so there are
+/// about twice as many items as structs.
+const MODULES: usize = 1_000;
+const STRUCTS_PER_MODULE: usize = 100;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// Keeps track of how many bytes are currently allocated on the heap, and the
+/// highest number of bytes that has been allocated at the same time.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::SeqCst);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let json_path = generate_rustdoc_json()?;
+    let json_size = std::fs::metadata(&json_path)?.len();
+    println!(
+        "Generated {} MB of rustdoc JSON with {} structs at {}\n",
+        to_mb(json_size),
+        MODULES * STRUCTS_PER_MODULE,
+        json_path.display()
+    );
+
+    measure("public_api_from_rustdoc_json_str", || {
+        let json = std::fs::read_to_string(&json_path)?;
+        Ok(public_api_from_rustdoc_json_str(&json, Options::default())?)
+    })?;
+    measure("public_api_from_rustdoc_json_reader", || {
+        let file = File::open(&json_path)?;
+        Ok(public_api_from_rustdoc_json_reader(
+            file,
+            Options::default(),
+        )?)
+    })?;
+    measure("public_api_from_rustdoc_json_path", || {
+        Ok(public_api_from_rustdoc_json_path(
+            &json_path,
+            Options::default(),
+        )?)
+    })?;
+    measure("public_api_from_rustdoc_json_mmap", || {
+        // SAFETY: Nothing else touches the generated rustdoc JSON file
+        Ok(unsafe { public_api_from_rustdoc_json_mmap(&json_path, Options::default())? })
+    })?;
+
+    Ok(())
+}
+
+/// Runs `f` and prints the peak heap memory usage while it ran, not counting
+/// memory that was already allocated before.
+fn measure(
+    name: &str,
+    f: impl FnOnce() -> Result<PublicApi, Box<dyn std::error::Error>>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let baseline = ALLOCATED.load(Ordering::SeqCst);
+    PEAK.store(baseline, Ordering::SeqCst);
+    let start = Instant::now();
+
+    let public_api = f()?;
+
+    let elapsed = start.elapsed();
+    let peak = PEAK.load(Ordering::SeqCst) - baseline;
+    println!(
+        "{name:<40} peak heap: {:>5} MB   time: {elapsed:>8.2?}   items: {}",
+        to_mb(peak as u64),
+        public_api.items.len()
+    );
+
+    Ok(())
+}
+
+/// Writes the rustdoc JSON of a synthetic crate to a file and returns the path.
+fn generate_rustdoc_json() -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let json_path = Path::new(env!("CARGO_TARGET_TMPDIR")).join("peak_memory_bench.json");
+    let mut writer = BufWriter::new(File::create(&json_path)?);
+    common::write_rustdoc_json(&mut writer, MODULES, STRUCTS_PER_MODULE)?;
+    writer.flush()?;
+
+    Ok(json_path)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn to_mb(bytes: u64) -> String {
+    format!("{:.0}", bytes as f64 / 1_000_000.0)
+}
@@ -0,0 +1,145 @@
+use std::path::{Path, PathBuf};
+
+use rustdoc_json::BuildOptions;
+
+use crate::{public_api_from_rustdoc_json_path, Options, PublicApi, Result};
+
+impl PublicApi {
+    /// Returns a [`PublicApiBuilder`] that builds rustdoc JSON for a library
+    /// crate and collects its public API in one go. Like this:
+    ///
+    /// ```no_run
+    /// let public_api = public_api::PublicApi::builder()
+    ///     .manifest_path("Cargo.toml")
+    ///     .toolchain("+nightly".to_owned())
+    ///     .build()?;
+    ///
+    /// for public_item in public_api.items {
+    ///     println!("{}", public_item);
+    /// }
+    /// # Ok::<(), public_api::Error>(())
+    /// ```
+    ///
+    /// Requires the `build` cargo feature.
+    #[must_use]
+    pub fn builder() -> PublicApiBuilder {
+        PublicApiBuilder::default()
+    }
+}
+
+/// Builds rustdoc JSON with [`rustdoc_json::build()`] and then collects the
+/// public API from it with [`public_api_from_rustdoc_json_path`]. Create it with
+/// [`PublicApi::builder()`].
+///
+/// Requires the `build` cargo feature.
+#[derive(Debug, Default)]
+pub struct PublicApiBuilder {
+    build_options: BuildOptions,
+    options: Options,
+}
+
+impl PublicApiBuilder {
+    /// Set the toolchain to build rustdoc JSON with. Default: `None`. See
+    /// [`BuildOptions::toolchain`].
+    #[must_use]
+    pub fn toolchain(mut self, toolchain: impl Into<Option<String>>) -> Self {
+        self.build_options = self.build_options.toolchain(toolchain);
+        self
+    }
+
+    /// Set the relative or absolute path to `Cargo.toml`. Default:
+    /// `Cargo.toml`. See [`BuildOptions::manifest_path`].
+    #[must_use]
+    pub fn manifest_path(mut self, manifest_path: impl AsRef<Path>) -> Self {
+        self.build_options = self.build_options.manifest_path(manifest_path);
+        self
+    }
+
+    /// Whether or not to build rustdoc JSON quietly. Default: `false`. See
+    /// [`BuildOptions::quiet`].
+    #[must_use]
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.build_options = self.build_options.quiet(quiet);
+        self
+    }
+
+    /// Set the target triple to build rustdoc JSON for. Default: `None`. See
+    /// [`BuildOptions::target`].
+    #[must_use]
+    pub fn target(mut self, target: String) -> Self {
+        self.build_options = self.build_options.target(target);
+        self
+    }
+
+    /// Whether or not to build without default features. Default: `false`.
+    /// See [`BuildOptions::no_default_features`].
+    #[must_use]
+    pub fn no_default_features(mut self, no_default_features: bool) -> Self {
+        self.build_options = self.build_options.no_default_features(no_default_features);
+        self
+    }
+
+    /// Whether or not to build with all features. Default: `false`. See
+    /// [`BuildOptions::all_features`].
+    #[must_use]
+    pub fn all_features(mut self, all_features: bool) -> Self {
+        self.build_options = self.build_options.all_features(all_features);
+        self
+    }
+
+    /// Features to build with. Default: an empty vector. See
+    /// [`BuildOptions::features`].
+    #[must_use]
+    pub fn features<I: IntoIterator<Item = S>, S: AsRef<str>>(mut self, features: I) -> Self {
+        self.build_options = self.build_options.features(features);
+        self
+    }
+
+    /// Package of a workspace to build. Default: `None`. See
+    /// [`BuildOptions::package`].
+    #[must_use]
+    pub fn package(mut self, package: impl AsRef<str>) -> Self {
+        self.build_options = self.build_options.package(package);
+        self
+    }
+
+    /// Replace all options for building rustdoc JSON at once. Handy if you
+    /// already have [`BuildOptions`] for use with [`rustdoc_json::build()`].
+    #[must_use]
+    pub fn build_options(mut self, build_options: BuildOptions) -> Self {
+        self.build_options = build_options;
+        self
+    }
+
+    /// Set the [`Options`] to collect the public API with. Default:
+    /// [`Options::default()`].
+    #[must_use]
+    pub fn options(mut self, options: Options) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Builds rustdoc JSON and collects the public API from it.
+    ///
+    /// # Errors
+    ///
+    /// E.g. if building the rustdoc JSON fails, or if the rustdoc JSON can not
+    /// be parsed.
+    pub fn build(self) -> Result<PublicApi> {
+        self.build_with_rustdoc_json_path()
+            .map(|(public_api, _)| public_api)
+    }
+
+    /// Like [`Self::build()`], but also returns the path to the rustdoc JSON
+    /// that the public API was collected from.
+    ///
+    /// # Errors
+    ///
+    /// See [`Self::build()`].
+    pub fn build_with_rustdoc_json_path(self) -> Result<(PublicApi, PathBuf)> {
+        let json_path = rustdoc_json::build(self.build_options)?;
+
+        let public_api = public_api_from_rustdoc_json_path(&json_path, self.options)?;
+        Ok((public_api, json_path))
+    }
+}
@@ -0,0 +1,72 @@
+use std::fmt::Display;
+
+/// Something noteworthy that happened while collecting the public API, such as
+/// an item that could not be found in the rustdoc JSON. Diagnostics do not
+/// end up in [`crate::PublicApi::items`]. They are intended for use with
+/// `--verbose` flags or similar, to explain why some item might be missing
+/// from the public API.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive] // More fields might be added in the future
+pub struct Diagnostic {
+    /// What happened.
+    pub kind: DiagnosticKind,
+
+    /// The path of the affected item, e.g. `your_crate::mod_a::Item`.
+    pub path: String,
+
+    /// The rustdoc JSON Id of the affected item. The exact format of Ids are
+    /// to be considered an implementation detail and must not be relied on.
+    pub id: String,
+}
+
+/// The different kinds of [`Diagnostic`]s.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[non_exhaustive] // More kinds are likely to be added in the future
+pub enum DiagnosticKind {
+    /// An item is referenced, but is missing from the rustdoc JSON. This
+    /// typically happens for re-exports of items from other crates, see
+    /// <https://github.com/rust-lang/rust/issues/99513>.
+    MissingItem,
+
+    /// The items of a `pub use source::*` could not be listed, because the
+    /// module they are imported from is missing from the rustdoc JSON.
+    UnresolvedGlobImport {
+        /// The `source` of `pub use source::*`.
+        source: String,
+    },
+
+    /// A `pub use source::*` was not inlined because it directly or
+    /// indirectly imports the module it is in. The items of the module are
+    /// already listed where the module is first encountered.
+    GlobImportCycle {
+        /// The `source` of `pub use source::*`.
+        source: String,
+    },
+
+    /// An item without a name was skipped, since there is no path to list it
+    /// under.
+    MissingName,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = &self.path;
+        let id = &self.id;
+        match &self.kind {
+            DiagnosticKind::MissingItem => {
+                write!(f, "`{path}` is missing from the rustdoc JSON (Id {id})")
+            }
+            DiagnosticKind::UnresolvedGlobImport { source } => write!(
+                f,
+                "`pub use {source}::*` in `{path}` could not be resolved, because `{source}` is missing from the rustdoc JSON (Id {id})"
+            ),
+            DiagnosticKind::GlobImportCycle { source } => write!(
+                f,
+                "`pub use {source}::*` in `{path}` was not inlined, because it imports itself (Id {id})"
+            ),
+            DiagnosticKind::MissingName => {
+                write!(f, "Skipped item without a name in `{path}` (Id {id})")
+            }
+        }
+    }
+}
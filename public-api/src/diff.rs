@@ -290,13 +290,14 @@ mod tests {
     }
 
     fn item_with_path(path: &str) -> PublicItem {
-        PublicItem {
-            path: path
-                .split("::")
+        PublicItem::new(
+            path.split("::")
                 .map(std::string::ToString::to_string)
                 .collect(),
-            tokens: vec![crate::tokens::Token::identifier(path)],
-        }
+            vec![crate::tokens::Token::identifier(path)],
+            None,
+            None,
+        )
     }
 
     fn fn_with_param_type(path_str: &[&str], type_: &str) -> PublicItem {
@@ -318,7 +319,7 @@ mod tests {
         tokens.extend(vec![q("("), i("x"), s(":"), w(), t(type_), q(")")]);
 
         // End result is e.g. "pub fn a::b(x: usize)"
-        PublicItem { path, tokens }
+        PublicItem::new(path, tokens, None, None)
     }
 
     fn s(s: &str) -> Token {
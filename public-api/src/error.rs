@@ -9,6 +9,31 @@ pub enum Error {
     /// too old. Consult the "Compatibility matrix" in the README.
     #[error(transparent)]
     SerdeJsonError(#[from] serde_json::Error),
+
+    /// Occurs if building rustdoc JSON fails. See
+    /// [`rustdoc_json::BuildError`] for details. Requires the `build` cargo
+    /// feature.
+    #[cfg(feature = "build")]
+    #[error(transparent)]
+    BuildError(#[from] rustdoc_json::BuildError),
+
+    /// Occurs if the rustdoc JSON has a `format_version` that this version of
+    /// the library can't parse. Typically because the nightly toolchain that
+    /// built the rustdoc JSON is too old or too new. The error message
+    /// explains what nightly toolchains or cargo features to use instead.
+    #[error("{}", crate::format_versions::unsupported_format_version_message(*.found, .supported))]
+    UnsupportedFormatVersion {
+        /// The `format_version` of the rustdoc JSON.
+        found: u32,
+
+        /// The `format_version`s that this version of the library supports
+        /// with the enabled cargo features, in ascending order.
+        supported: Vec<u32>,
+    },
+
+    /// Some kind of IO error occurred, e.g. when reading rustdoc JSON.
+    #[error(transparent)]
+    IoError(#[from] std::io::Error),
 }
 
 /// Shorthand for [`std::result::Result<T, public_api::Error>`].
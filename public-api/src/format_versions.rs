@@ -0,0 +1,309 @@
+//! The rustdoc JSON format changes every now and then, and each change bumps
+//! its `format_version`. We process one format internally, namely the one of
+//! the `rustdoc-types` version we depend on. Other recent formats can be
+//! enabled with cargo features, e.g. `format-version-20`. Such formats are
+//! deserialized with the `rustdoc-types` version made for them, and then
+//! normalized to the format we process.
+//!
+//! The formats only differ in a few places, so normalization is done by
+//! adjusting the serialized form of the differing parts, and then
+//! deserializing the result in the format we process.
+
+use std::io::{BufReader, Read};
+
+use rustdoc_types::{Crate, FORMAT_VERSION};
+use serde::de::DeserializeOwned;
+use serde_json::de::{IoRead, SliceRead};
+
+use crate::{Error, Result};
+
+/// The format versions we know about, in ascending order. For each, a nightly
+/// toolchain that builds rustdoc JSON of that format version, and the cargo
+/// feature that enables it, unless it is the format we process internally.
+/// Keep in sync with the `format-version-*` features in `Cargo.toml`.
+const KNOWN_FORMAT_VERSIONS: &[(u32, &str, Option<&str>)] = &[
+    (20, "nightly-2022-09-07", Some("format-version-20")),
+    (FORMAT_VERSION, crate::MINIMUM_RUSTDOC_JSON_VERSION, None),
+    (22, "nightly-2022-10-15", Some("format-version-22")),
+];
+
+/// All format versions that can be processed with the enabled cargo features,
+/// in ascending order.
+pub(crate) fn supported_format_versions() -> Vec<u32> {
+    let mut supported = vec![FORMAT_VERSION];
+    #[cfg(feature = "format-version-20")]
+    supported.push(v20::FORMAT_VERSION);
+    #[cfg(feature = "format-version-22")]
+    supported.push(v22::FORMAT_VERSION);
+    supported.sort_unstable();
+    supported
+}
+
+/// Explains why rustdoc JSON of format version `found` can not be processed,
+/// and what to do about it. Used by [`Error::UnsupportedFormatVersion`].
+pub(crate) fn unsupported_format_version_message(found: u32, supported: &[u32]) -> String {
+    let supported_list = supported
+        .iter()
+        .map(|version| match nightly_for(*version) {
+            Some(nightly) => format!("{version} ({nightly})"),
+            None => version.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let intro = format!(
+        "The rustdoc JSON has format version {found}, but only format versions \
+        {supported_list} are supported."
+    );
+
+    if let Some((_, _, Some(feature))) = known_format_version(found) {
+        return format!(
+            "{intro} Enable the `{feature}` cargo feature of `public-api` to support it."
+        );
+    }
+
+    let oldest = supported.first().copied().unwrap_or(FORMAT_VERSION);
+    let newest = supported.last().copied().unwrap_or(FORMAT_VERSION);
+    let toolchains = match (nightly_for(oldest), nightly_for(newest)) {
+        (Some(first), Some(last)) if first != last => {
+            format!("a nightly toolchain from {first} to {last}")
+        }
+        (Some(nightly), _) | (_, Some(nightly)) => nightly.to_owned(),
+        (None, None) => String::from("a nightly toolchain with a supported format version"),
+    };
+    let (age, alternative) = if found < oldest {
+        ("old", String::new())
+    } else {
+        (
+            "new",
+            format!(", or upgrade to a release that supports format version {found}"),
+        )
+    };
+    format!("{intro} The format version is too {age}. Build the rustdoc JSON with {toolchains}{alternative}.")
+}
+
+fn known_format_version(
+    format_version: u32,
+) -> Option<&'static (u32, &'static str, Option<&'static str>)> {
+    KNOWN_FORMAT_VERSIONS
+        .iter()
+        .find(|(version, _, _)| *version == format_version)
+}
+
+/// A nightly toolchain that builds rustdoc JSON of `format_version`, if we know
+/// one.
+fn nightly_for(format_version: u32) -> Option<&'static str> {
+    known_format_version(format_version).map(|(_, nightly, _)| *nightly)
+}
+
+/// Deserializes rustdoc JSON of any supported format version into the format
+/// we process. If the format version is not supported, we return
+/// [`Error::UnsupportedFormatVersion`] before we try to deserialize all of it.
+/// Otherwise the user would get a cryptic deserialization error. If there is
+/// no `format_version` at all, we let the regular deserialization report the
+/// problem.
+pub(crate) fn deserialize_crate(rustdoc_json: &[u8]) -> Result<Crate> {
+    match format_version(rustdoc_json) {
+        None | Some(FORMAT_VERSION) => {
+            deserialize_without_recursion_limit(SliceRead::new(rustdoc_json))
+        }
+        #[cfg(feature = "format-version-20")]
+        Some(v20::FORMAT_VERSION) => v20::deserialize_crate(rustdoc_json),
+        #[cfg(feature = "format-version-22")]
+        Some(v22::FORMAT_VERSION) => v22::deserialize_crate(rustdoc_json),
+        Some(found) => Err(unsupported_format_version(found)),
+    }
+}
+
+/// Deserializes rustdoc JSON from a reader without first reading all of it
+/// into memory. Since rustdoc puts `format_version` last, we can not look at
+/// it before we deserialize, and since we can not rewind the reader, only the
+/// format version we process internally is supported.
+pub(crate) fn deserialize_crate_from_reader(reader: impl Read) -> Result<Crate> {
+    let crate_: Crate = deserialize_without_recursion_limit(IoRead::new(BufReader::new(reader)))?;
+    if crate_.format_version != FORMAT_VERSION {
+        return Err(unsupported_format_version(crate_.format_version));
+    }
+
+    Ok(crate_)
+}
+
+fn unsupported_format_version(found: u32) -> Error {
+    Error::UnsupportedFormatVersion {
+        found,
+        supported: supported_format_versions(),
+    }
+}
+
+/// Reads only the `format_version` of rustdoc JSON. Rustdoc puts it last, so
+/// we first look for it at the end of the JSON, which is practically free.
+/// Only if it is not there do we fall back to going through all of the JSON,
+/// skipping over the rest without allocating it.
+fn format_version(rustdoc_json: &[u8]) -> Option<u32> {
+    #[derive(serde::Deserialize)]
+    struct FormatVersion {
+        format_version: u32,
+    }
+
+    trailing_format_version(rustdoc_json).or_else(|| {
+        deserialize_without_recursion_limit::<FormatVersion, _>(SliceRead::new(rustdoc_json))
+            .ok()
+            .map(|v| v.format_version)
+    })
+}
+
+/// Parses `,"format_version":N}` at the very end of the JSON, allowing for
+/// whitespace between tokens. Returns `None` if the JSON does not end like
+/// that.
+fn trailing_format_version(rustdoc_json: &[u8]) -> Option<u32> {
+    let json = trim_end(trim_end(rustdoc_json).strip_suffix(b"}")?);
+    let digits_start = json
+        .iter()
+        .rposition(|b| !b.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let version = std::str::from_utf8(&json[digits_start..])
+        .ok()?
+        .parse()
+        .ok()?;
+    let json = trim_end(trim_end(&json[..digits_start]).strip_suffix(b":")?);
+    let json = trim_end(json.strip_suffix(b"\"format_version\"")?);
+    (json.ends_with(b",") || json.ends_with(b"{")).then_some(version)
+}
+
+fn trim_end(json: &[u8]) -> &[u8] {
+    let end = json
+        .iter()
+        .rposition(|b| !b.is_ascii_whitespace())
+        .map_or(0, |i| i + 1);
+    &json[..end]
+}
+
+/// Helper to deserialize the JSON with `serde_json`, but with the recursion
+/// limit disabled. Otherwise we hit the recursion limit on crates such as
+/// `diesel`.
+fn deserialize_without_recursion_limit<'de, T: DeserializeOwned, R: serde_json::de::Read<'de>>(
+    read: R,
+) -> Result<T> {
+    let mut deserializer = serde_json::Deserializer::new(read);
+    deserializer.disable_recursion_limit();
+    Ok(serde::de::Deserialize::deserialize(&mut deserializer)?)
+}
+
+/// Deserializes rustdoc JSON with the `rustdoc-types` version `C` is from, and
+/// then normalizes each serialized item with `normalize_item` into the format
+/// we process.
+#[cfg(any(feature = "format-version-20", feature = "format-version-22"))]
+fn deserialize_and_normalize<C: DeserializeOwned + serde::Serialize>(
+    rustdoc_json: &[u8],
+    normalize_item: impl Fn(&mut serde_json::Value),
+) -> Result<Crate> {
+    let crate_: C = deserialize_without_recursion_limit(SliceRead::new(rustdoc_json))?;
+
+    let mut value = serde_json::to_value(crate_)?;
+    if let Some(index) = value
+        .get_mut("index")
+        .and_then(serde_json::Value::as_object_mut)
+    {
+        index.values_mut().for_each(normalize_item);
+    }
+    value["format_version"] = FORMAT_VERSION.into();
+
+    Ok(serde_json::from_value(value)?)
+}
+
+/// Format version 20, built by nightly-2022-09-07. Differs in how struct
+/// fields are represented.
+#[cfg(feature = "format-version-20")]
+mod v20 {
+    use serde_json::{json, Value};
+
+    pub(super) use rustdoc_types_v20::FORMAT_VERSION;
+
+    pub(super) fn deserialize_crate(rustdoc_json: &[u8]) -> crate::Result<rustdoc_types::Crate> {
+        super::deserialize_and_normalize::<rustdoc_types_v20::Crate>(rustdoc_json, normalize_item)
+    }
+
+    /// Turns `struct_type`, `fields` and `fields_stripped` of structs into
+    /// `kind`. Stripped fields of tuple structs are not represented in format
+    /// version 20, so we can only list the fields that are there.
+    fn normalize_item(item: &mut Value) {
+        if item["kind"] != "struct" {
+            return;
+        }
+
+        if let Some(inner) = item["inner"].as_object_mut() {
+            let struct_type = inner.remove("struct_type").unwrap_or(Value::Null);
+            let fields = inner.remove("fields").unwrap_or_else(|| json!([]));
+            let fields_stripped = inner
+                .remove("fields_stripped")
+                .unwrap_or(Value::Bool(false));
+
+            let kind = match struct_type.as_str() {
+                Some("unit") => json!("unit"),
+                Some("tuple") => json!({ "tuple": fields }),
+                _ => json!({ "plain": { "fields": fields, "fields_stripped": fields_stripped } }),
+            };
+            inner.insert(String::from("kind"), kind);
+        }
+    }
+}
+
+/// Format version 22, built by nightly-2022-10-15. Differs in how primitive
+/// types are represented.
+#[cfg(feature = "format-version-22")]
+mod v22 {
+    use serde_json::Value;
+
+    pub(super) use rustdoc_types_v22::FORMAT_VERSION;
+
+    pub(super) fn deserialize_crate(rustdoc_json: &[u8]) -> crate::Result<rustdoc_types::Crate> {
+        super::deserialize_and_normalize::<rustdoc_types_v22::Crate>(rustdoc_json, normalize_item)
+    }
+
+    /// Turns `primitive` items with a name and impls into `primitive_type`
+    /// items with just a name. The impls are found via the impls themselves
+    /// anyway.
+    fn normalize_item(item: &mut Value) {
+        if item["kind"] != "primitive" {
+            return;
+        }
+
+        let name = item["inner"]["name"].take();
+        item["kind"] = Value::from("primitive_type");
+        item["inner"] = name;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_format_version_is_found() {
+        assert_eq!(
+            trailing_format_version(br#"{"a":1,"format_version":21}"#),
+            Some(21)
+        );
+        assert_eq!(
+            trailing_format_version(b"{\n  \"format_version\" : 21\n}\n"),
+            Some(21)
+        );
+    }
+
+    #[test]
+    fn format_version_that_is_not_last_is_found() {
+        assert_eq!(
+            trailing_format_version(br#"{"format_version":21,"a":1}"#),
+            None
+        );
+        assert_eq!(format_version(br#"{"format_version":21,"a":1}"#), Some(21));
+    }
+
+    #[test]
+    fn nested_format_version_is_ignored() {
+        assert_eq!(
+            trailing_format_version(br#"{"a":{"format_version":21}}"#),
+            None
+        );
+        assert_eq!(format_version(br#"{"a":{"format_version":21}}"#), None);
+    }
+}
@@ -1,9 +1,11 @@
+use std::borrow::Cow;
+use std::sync::Arc;
+
 use crate::render;
-use std::rc::Rc;
 
 use rustdoc_types::{Id, Item, Type};
 
-use crate::tokens::Token;
+use crate::{tokens::Token, ReExport};
 
 /// This struct represents one public item of a crate, but in intermediate form.
 /// It wraps a single [Item] but adds additional calculated values to make it
@@ -14,10 +16,6 @@ pub struct IntermediatePublicItem<'a> {
     /// The item we are effectively wrapping.
     pub item: &'a Item,
 
-    /// The name of the item. Normally this is [Item::name]. But in the case of
-    /// renamed imports (`pub use other::item as foo;`) it is the new name.
-    pub name: String,
-
     /// Sometimes an item references other items via ID that we do not include
     /// in the output. Currently this only happens for tuple structs (both
     /// regular and enum variants). The reason we can get away with that is
@@ -41,48 +39,62 @@ pub struct IntermediatePublicItem<'a> {
     /// is what this field is for.
     pub pre_resolved_fields: Vec<Option<&'a Type>>,
 
-    /// The parent item. If [Self::item] is e.g. an enum variant, then the
-    /// parent is an enum. We follow the chain of parents to be able to know the
-    /// correct path to an item in the output.
-    parent: Option<Rc<IntermediatePublicItem<'a>>>,
+    /// Where the item is defined, if it is re-exported.
+    pub reexport: Option<ReExport>,
+
+    /// The path to the item, starting with the crate root and ending with the
+    /// item itself. If [`Self::item`] is e.g. an enum variant, then the second
+    /// to last component is the enum. Computed once up front from the path of
+    /// the parent item, since it is needed many times per item. The names are
+    /// borrowed from the rustdoc JSON, so this is cheap to build and to clone.
+    path: Arc<[PathComponent<'a>]>,
+}
+
+/// One component of the path to an [`IntermediatePublicItem`], e.g. `mod_a` in
+/// `your_crate::mod_a::Item`.
+#[derive(Clone, Debug)]
+pub struct PathComponent<'a> {
+    /// The item this component refers to.
+    pub item: &'a Item,
+
+    /// The name of the item. Normally this is [`Item::name`]. But in the case
+    /// of renamed imports (`pub use other::item as foo;`) it is the new name.
+    /// Only glob imports that could not be inlined get an owned name, e.g.
+    /// `other::*`.
+    pub name: Cow<'a, str>,
 }
 
 impl<'a> IntermediatePublicItem<'a> {
     #[must_use]
     pub fn new(
         item: &'a Item,
-        name: String,
+        name: Cow<'a, str>,
         pre_resolved_fields: Vec<Option<&'a Type>>,
-        parent: Option<Rc<IntermediatePublicItem<'a>>>,
+        reexport: Option<ReExport>,
+        parent: Option<&IntermediatePublicItem<'a>>,
     ) -> Self {
+        let path = parent
+            .into_iter()
+            .flat_map(|p| p.path.iter().cloned())
+            .chain(std::iter::once(PathComponent { item, name }))
+            .collect();
+
         Self {
             item,
-            name,
             pre_resolved_fields,
-            parent,
+            reexport,
+            path,
         }
     }
 
     #[must_use]
-    pub fn path(&'a self) -> Vec<Rc<IntermediatePublicItem<'a>>> {
-        let mut path = vec![];
-
-        let rc_self = Rc::new(self.clone());
-
-        path.insert(0, rc_self.clone());
-
-        let mut current_item = rc_self.clone();
-        while let Some(parent) = current_item.parent.clone() {
-            path.insert(0, parent.clone());
-            current_item = parent.clone();
-        }
-
-        path
+    pub fn path(&self) -> &[PathComponent<'a>] {
+        &self.path
     }
 
     #[must_use]
-    pub fn path_contains_id(&self, id: &'a Id) -> bool {
-        self.path().iter().any(|m| m.item.id == *id)
+    pub fn path_contains_id(&self, id: &Id) -> bool {
+        self.path.iter().any(|m| m.item.id == *id)
     }
 
     pub fn render_token_stream(&self) -> Vec<Token> {
@@ -1,11 +1,16 @@
-use std::{collections::HashMap, fmt::Display, rc::Rc};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt::Display,
+    path::{Path, PathBuf},
+};
 
 use rustdoc_types::{
     Crate, Id, Impl, Import, Item, ItemEnum, Module, Struct, StructKind, Type, Variant,
 };
 
 use super::intermediate_public_item::IntermediatePublicItem;
-use crate::{tokens::Token, Options, PublicApi};
+use crate::{render, tokens::Token, Diagnostic, DiagnosticKind, Options, PublicApi};
 
 type Impls<'a> = HashMap<&'a Id, Vec<&'a Impl>>;
 
@@ -42,15 +47,12 @@ pub struct ItemIterator<'a> {
     crate_: &'a Crate,
 
     /// What items left to visit (and possibly add more items from)
-    items_left: Vec<Rc<IntermediatePublicItem<'a>>>,
+    items_left: Vec<IntermediatePublicItem<'a>>,
 
-    /// Normally, an item referenced by item Id is present in the rustdoc JSON.
-    /// If [`Self::crate_.index`] is missing an Id, then we add it here, to aid
-    /// with debugging. It will typically be missing because of bugs (or
-    /// borderline bug such as re-exports of foreign items like discussed in
-    /// <https://github.com/rust-lang/rust/pull/99287#issuecomment-1186586518>)
-    /// We do not report it to users, because they can't do anything about it.
-    missing_ids: Vec<&'a Id>,
+    /// Things worth knowing about that happened while iterating, such as
+    /// missing items and glob imports that could not be inlined. See
+    /// [`Diagnostic`].
+    diagnostics: Vec<Diagnostic>,
 
     /// `impl`s are a bit special. They do not need to be reachable by the crate
     /// root in order to matter. All that matters is that the trait and type
@@ -72,7 +74,7 @@ impl<'a> ItemIterator<'a> {
         let mut s = ItemIterator {
             crate_,
             items_left: vec![],
-            missing_ids: vec![],
+            diagnostics: vec![],
             active_impls: active_impls(all_impls.clone(), options),
         };
 
@@ -82,7 +84,7 @@ impl<'a> ItemIterator<'a> {
         s
     }
 
-    fn add_children_for_item(&mut self, public_item: &Rc<IntermediatePublicItem<'a>>) {
+    fn add_children_for_item(&mut self, public_item: &IntermediatePublicItem<'a>) {
         // Handle any impls. See [`ItemIterator::impls`] docs for more info.
         let mut add_after_borrow = vec![];
         if let Some(impls) = self.active_impls.get(&public_item.item.id) {
@@ -93,79 +95,140 @@ impl<'a> ItemIterator<'a> {
             }
         }
         for id in add_after_borrow {
-            self.try_add_item_to_visit(id, Some(public_item.clone()));
+            self.try_add_item_to_visit(id, Some(public_item));
         }
 
         // Handle regular children of the item
         for child in items_in_container(public_item.item).into_iter().flatten() {
-            self.try_add_item_to_visit(child, Some(public_item.clone()));
+            self.try_add_item_to_visit(child, Some(public_item));
         }
     }
 
-    fn try_add_item_to_visit(
-        &mut self,
-        id: &'a Id,
-        parent: Option<Rc<IntermediatePublicItem<'a>>>,
-    ) {
-        match self.crate_.index.get(id) {
-            Some(item) => self.maybe_add_item_to_visit(item, parent),
-            None => self.add_missing_id(id),
+    fn try_add_item_to_visit(&mut self, id: &'a Id, parent: Option<&IntermediatePublicItem<'a>>) {
+        if let Some(item) = self.crate_.index.get(id) {
+            self.maybe_add_item_to_visit(item, parent);
+        } else {
+            let path = self.crate_.paths.get(id).map_or_else(
+                || path_string(parent, None),
+                |summary| summary.path.join("::"),
+            );
+            self.add_missing_id(id, path);
         }
     }
 
     fn maybe_add_item_to_visit(
         &mut self,
         item: &'a Item,
-        parent: Option<Rc<IntermediatePublicItem<'a>>>,
+        parent: Option<&IntermediatePublicItem<'a>>,
     ) {
-        // We try to inline glob imports, but that might fail, and we want to
-        // keep track of when that happens.
-        let mut glob_import_inlined = false;
-
         // We need to handle `pub use foo::*` specially. In case of such
         // wildcard imports, `glob` will be `true` and `id` will be the
         // module we should import all items from, but we should NOT add
         // the module itself.
-        if let ItemEnum::Import(Import {
-            id: Some(mod_id),
-            glob: true,
+        //
+        // We handle `impl`s specially, and we don't want to process `impl`
+        // items directly. See [`ItemIterator::impls`] docs for more info. All
+        // other items we can go ahead and add.
+        match &item.inner {
+            ItemEnum::Import(import) if import.glob => {
+                self.inline_glob_import(item, import, parent);
+            }
+            ItemEnum::Impl { .. } => {}
+            _ => self.add_item_to_visit(item, parent),
+        }
+    }
+
+    /// Inlines the items of the module that `item` glob imports. If that does
+    /// not result in any items, e.g. because the module is in another crate,
+    /// the `pub use …::*` item itself is listed instead, so that the glob
+    /// import still shows up in the public API.
+    fn inline_glob_import(
+        &mut self,
+        item: &'a Item,
+        import: &'a Import,
+        parent: Option<&IntermediatePublicItem<'a>>,
+    ) {
+        let items_before = self.items_left.len();
+        if let Some(kind) = self.try_inline_glob_import(import, parent) {
+            self.add_diagnostic(kind, path_string(parent, None), &item.id);
+        }
+        if self.items_left.len() == items_before {
+            self.add_item_to_visit(item, parent);
+        }
+    }
+
+    /// Returns what went wrong if the glob import could not be inlined.
+    fn try_inline_glob_import(
+        &mut self,
+        import: &'a Import,
+        parent: Option<&IntermediatePublicItem<'a>>,
+    ) -> Option<DiagnosticKind> {
+        let source = import.source.clone();
+        let mod_id = match &import.id {
+            Some(mod_id) => mod_id,
+            None => return Some(DiagnosticKind::UnresolvedGlobImport { source }),
+        };
+
+        // Before we inline this wildcard import, make sure that the module is
+        // not indirectly trying to import itself. If we allow that, we'll get
+        // a stack overflow. The items of the module are listed where the
+        // module was first encountered, so nothing is lost.
+        if parent.map_or(false, |p| p.path_contains_id(mod_id)) {
+            return Some(DiagnosticKind::GlobImportCycle { source });
+        }
+
+        if let Some(Item {
+            inner: ItemEnum::Module(Module { items, .. }),
             ..
-        }) = &item.inner
+        }) = self.crate_.index.get(mod_id)
         {
-            // Before we inline this wildcard import, make sure that the module
-            // is not indirectly trying to import itself. If we allow that,
-            // we'll get a stack overflow. Note that `glob_import_inlined`
-            // remains `false` in that case, which means that the output will
-            // use a special syntax to indicate that we broke recursion.
-            if !parent.clone().map_or(false, |p| p.path_contains_id(mod_id)) {
-                if let Some(Item {
-                    inner: ItemEnum::Module(Module { items, .. }),
-                    ..
-                }) = self.crate_.index.get(mod_id)
-                {
-                    for item in items {
-                        self.try_add_item_to_visit(item, parent.clone());
-                    }
-                    glob_import_inlined = true;
-                }
+            for item in items {
+                self.try_add_item_to_visit(item, parent);
             }
+            return None;
         }
 
-        // We handle `impl`s specially, and we don't want to process `impl`
-        // items directly. See [`ItemIterator::impls`] docs for more info. And
-        // if we inlined a glob import earlier, we should not add the import
-        // item itself. All other items we can go ahead and add.
-        if !glob_import_inlined && !matches!(item.inner, ItemEnum::Impl { .. }) {
-            self.add_item_to_visit(item, parent);
+        // The module is typically missing from the rustdoc JSON because it is
+        // in another crate. But rustdoc still knows about the items of the
+        // module that are referenced, so try to find them that way.
+        let children = self.children_in_paths(mod_id);
+        if children.is_empty() {
+            return Some(DiagnosticKind::UnresolvedGlobImport { source });
+        }
+        for child in children {
+            self.try_add_item_to_visit(child, parent);
         }
+        None
+    }
+
+    /// Uses [`Crate::paths`] to find the Ids of the items directly inside the
+    /// module with Id `mod_id`. Sorted by path, to get deterministic output.
+    fn children_in_paths(&self, mod_id: &Id) -> Vec<&'a Id> {
+        let module = match self.crate_.paths.get(mod_id) {
+            Some(module) => module,
+            None => return vec![],
+        };
+
+        let mut children = self
+            .crate_
+            .paths
+            .iter()
+            .filter(|(_, child)| {
+                child.crate_id == module.crate_id
+                    && child.path.len() == module.path.len() + 1
+                    && child.path.starts_with(&module.path)
+            })
+            .collect::<Vec<_>>();
+        children.sort_by(|(_, a), (_, b)| a.path.cmp(&b.path));
+        children.into_iter().map(|(id, _)| id).collect()
     }
 
     fn add_item_to_visit(
         &mut self,
         mut item: &'a Item,
-        parent: Option<Rc<IntermediatePublicItem<'a>>>,
+        parent: Option<&IntermediatePublicItem<'a>>,
     ) {
-        let mut name = item.name.clone();
+        let mut name = item.name.as_deref().map(Cow::Borrowed);
 
         // Since public imports are part of the public API, we inline them, i.e.
         // replace the item corresponding to an import with the item that is
@@ -174,40 +237,87 @@ impl<'a> ItemIterator<'a> {
         // the use cases of this tool. We want to show the actual API, and thus
         // also show type information! There is one exception; for re-exports of
         // primitive types, there is no item Id to inline with, so they remain
-        // as e.g. `pub use my_i32` in the output.
+        // as e.g. `pub use my_i32` in the output. Glob imports are inlined in
+        // inline_glob_import(), and only end up here if that did not result in
+        // any items.
         if let ItemEnum::Import(import) = &item.inner {
-            name = if import.glob {
-                // Items should have been inlined in maybe_add_item_to_visit(),
-                // but since we got here that must have failed, typically
-                // because the built rustdoc JSON omitted some items from the
-                // output, or to break import recursion.
-                Some(format!("<<{}::*>>", import.source))
+            if import.glob {
+                name = Some(Cow::Owned(format!("{}::*", import.source)));
             } else {
                 if let Some(imported_id) = &import.id {
-                    if !parent
-                        .clone()
-                        .map_or(false, |p| p.path_contains_id(imported_id))
-                    {
-                        match self.crate_.index.get(imported_id) {
-                            Some(imported_item) => item = imported_item,
-                            None => self.add_missing_id(imported_id),
+                    if !parent.map_or(false, |p| p.path_contains_id(imported_id)) {
+                        if let Some(imported_item) = self.crate_.index.get(imported_id) {
+                            item = imported_item;
+                        } else {
+                            let path = path_string(parent, Some(&import.name));
+                            self.add_missing_id(imported_id, path);
                         }
                     }
                 }
-                Some(import.name.clone())
-            };
+                name = Some(Cow::Borrowed(&import.name));
+            }
         }
 
-        let public_item = Rc::new(IntermediatePublicItem::new(
+        let name = if let Some(name) = name {
+            name
+        } else {
+            let path = path_string(parent, None);
+            self.add_diagnostic(DiagnosticKind::MissingName, path, &item.id);
+            return;
+        };
+
+        let reexport = self.reexport_of(item, &name, parent);
+        let public_item = IntermediatePublicItem::new(
             item,
-            name.unwrap_or_else(|| String::from("<<no_name>>")),
+            name,
             self.pre_resolved_fields_for_item(item),
+            reexport,
             parent,
-        ));
+        );
 
         self.items_left.push(public_item);
     }
 
+    /// If `item` is publicly available under another path than where it is
+    /// defined, i.e. if it is re-exported, returns where it is defined. Items
+    /// that are re-exported but missing from the rustdoc JSON, such as many
+    /// foreign items, are still represented by their import, so in that case
+    /// we look up the imported item instead.
+    fn reexport_of(
+        &self,
+        item: &'a Item,
+        name: &str,
+        parent: Option<&IntermediatePublicItem<'a>>,
+    ) -> Option<ReExport> {
+        let original_id = match &item.inner {
+            ItemEnum::Import(Import {
+                id: Some(imported_id),
+                glob: false,
+                ..
+            }) => imported_id,
+            ItemEnum::Import(_) => return None,
+            _ => &item.id,
+        };
+        let summary = self.crate_.paths.get(original_id)?;
+
+        let parent_path = parent.map_or(&[][..], IntermediatePublicItem::path);
+        if summary.path.len() == parent_path.len() + 1
+            && summary.path.last().map(String::as_str) == Some(name)
+            && summary
+                .path
+                .iter()
+                .zip(parent_path)
+                .all(|(a, b)| *a == b.name)
+        {
+            return None;
+        }
+
+        Some(ReExport {
+            original_path: summary.path.clone(),
+            external: summary.crate_id != 0,
+        })
+    }
+
     /// See [`IntermediatePublicItem::pre_resolved_fields`] docs for more info.
     fn pre_resolved_fields_for_item(&self, item: &'a Item) -> Vec<Option<&'a Type>> {
         let mut pre_resolved_fields: Vec<Option<&Type>> = vec![];
@@ -240,19 +350,27 @@ impl<'a> ItemIterator<'a> {
         pre_resolved_fields
     }
 
-    fn add_missing_id(&mut self, id: &'a Id) {
-        self.missing_ids.push(id);
+    fn add_missing_id(&mut self, id: &Id, path: String) {
+        self.add_diagnostic(DiagnosticKind::MissingItem, path, id);
+    }
+
+    fn add_diagnostic(&mut self, kind: DiagnosticKind, path: String, id: &Id) {
+        self.diagnostics.push(Diagnostic {
+            kind,
+            path,
+            id: id.0.clone(),
+        });
     }
 }
 
 impl<'a> Iterator for ItemIterator<'a> {
-    type Item = Rc<IntermediatePublicItem<'a>>;
+    type Item = IntermediatePublicItem<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut result = None;
 
         if let Some(public_item) = self.items_left.pop() {
-            self.add_children_for_item(&public_item.clone());
+            self.add_children_for_item(&public_item);
 
             result = Some(public_item);
         }
@@ -261,7 +379,7 @@ impl<'a> Iterator for ItemIterator<'a> {
     }
 }
 
-fn all_impls(crate_: &Crate) -> impl Iterator<Item = ImplItem> {
+fn all_impls(crate_: &Crate) -> impl Iterator<Item = ImplItem<'_>> {
     crate_.index.values().filter_map(|item| match &item.inner {
         ItemEnum::Impl(impl_) => Some(ImplItem {
             impl_,
@@ -276,7 +394,7 @@ fn all_impls(crate_: &Crate) -> impl Iterator<Item = ImplItem> {
 }
 
 fn impl_kind(impl_: &Impl) -> ImplKind {
-    let has_blanket_impl = matches!(impl_.blanket_impl, Some(_));
+    let has_blanket_impl = impl_.blanket_impl.is_some();
 
     // See https://github.com/rust-lang/rust/blob/54f20bbb8a7aeab93da17c0019c1aaa10329245a/src/librustdoc/json/conversions.rs#L589-L590
     match (impl_.synthetic, has_blanket_impl) {
@@ -330,52 +448,244 @@ fn items_in_container(item: &Item) -> Option<&Vec<Id>> {
 
 pub fn public_api_in_crate(crate_: &Crate, options: Options) -> super::PublicApi {
     let mut item_iterator = ItemIterator::new(crate_, options);
-    let items = item_iterator
-        .by_ref()
-        .map(|p| intermediate_public_item_to_public_item(&p))
+    let mut items = vec![];
+    for public_item in item_iterator.by_ref() {
+        if options.with_reexports && !is_inlinable_import(crate_, public_item.item) {
+            if let Some(reexport) = &public_item.reexport {
+                items.push(PublicItem::new(
+                    public_item_path(&public_item),
+                    render::reexport_token_stream(&public_item, reexport),
+                    Some(reexport.clone()),
+                    span_of(&public_item),
+                ));
+            }
+        }
+        items.push(intermediate_public_item_to_public_item(&public_item));
+    }
+
+    let missing_item_ids = item_iterator
+        .diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.kind == DiagnosticKind::MissingItem)
+        .map(|diagnostic| diagnostic.id.clone())
         .collect();
 
+    #[allow(deprecated)]
     PublicApi {
         items,
-        missing_item_ids: item_iterator
-            .missing_ids
-            .iter()
-            .map(|m| m.0.clone())
-            .collect(),
+        missing_item_ids,
+        warnings: item_iterator.diagnostics,
     }
 }
 
-fn intermediate_public_item_to_public_item(
-    public_item: &Rc<IntermediatePublicItem<'_>>,
-) -> PublicItem {
-    PublicItem {
-        path: public_item
+/// Returns `true` if `item` is an import of an item in the rustdoc JSON that
+/// was not inlined, which only happens for imports that would cause a cycle.
+/// Imports of items missing from the rustdoc JSON, such as foreign items, are
+/// how foreign re-exports show up, so for those we return `false`.
+fn is_inlinable_import(crate_: &Crate, item: &Item) -> bool {
+    match &item.inner {
+        ItemEnum::Import(Import {
+            id: Some(imported_id),
+            ..
+        }) => crate_.index.contains_key(imported_id),
+        _ => false,
+    }
+}
+
+fn intermediate_public_item_to_public_item(public_item: &IntermediatePublicItem<'_>) -> PublicItem {
+    PublicItem::new(
+        public_item_path(public_item),
+        public_item.render_token_stream(),
+        public_item.reexport.clone(),
+        span_of(public_item),
+    )
+}
+
+fn span_of(public_item: &IntermediatePublicItem<'_>) -> Option<Span> {
+    public_item.item.span.as_ref().map(|span| Span {
+        filename: span.filename.clone(),
+        begin: span.begin,
+        end: span.end,
+    })
+}
+
+/// Joins the path of `parent` and `name` to e.g. `your_crate::mod_a::name`.
+fn path_string(parent: Option<&IntermediatePublicItem<'_>>, name: Option<&str>) -> String {
+    let mut path = parent.map_or_else(Vec::new, |parent| {
+        parent
             .path()
             .iter()
-            .map(|i| i.name.clone())
-            .collect::<PublicItemPath>(),
-        tokens: public_item.render_token_stream(),
-    }
+            .map(|p| p.name.as_ref())
+            .collect::<Vec<_>>()
+    });
+    path.extend(name);
+    path.join("::")
+}
+
+fn public_item_path(public_item: &IntermediatePublicItem<'_>) -> PublicItemPath {
+    public_item
+        .path()
+        .iter()
+        .map(|i| i.name.to_string())
+        .collect::<PublicItemPath>()
 }
 
 /// Represent a public item of an analyzed crate, i.e. an item that forms part
 /// of the public API of a crate. Implements [`Display`] so it can be printed. It
 /// also implements [`Ord`], but how items are ordered are not stable yet, and
 /// will change in later versions.
-#[derive(Clone, Eq, PartialEq, Hash)]
+#[derive(Clone)]
 pub struct PublicItem {
-    /// The "your_crate::mod_a::mod_b" part of an item. Split by "::"
+    /// The "`your_crate::mod_a::mod_b`" part of an item. Split by "::"
     pub(crate) path: PublicItemPath,
 
     /// The rendered item as a stream of [`Token`]s
     pub(crate) tokens: Vec<Token>,
+
+    /// Where the item is defined, if it is re-exported. Not part of equality,
+    /// since it is not part of how the item can be used.
+    pub(crate) reexport: Option<ReExport>,
+
+    /// Where in the source code the item is defined. Not part of equality,
+    /// since moving an item around does not change the public API.
+    pub(crate) span: Option<Span>,
+
+    /// The tokens rendered as text. Rendered once up front since it is what
+    /// we sort by and what we display, and sorting compares items many times.
+    rendered: String,
 }
 
 impl PublicItem {
+    pub(crate) fn new(
+        path: PublicItemPath,
+        tokens: Vec<Token>,
+        reexport: Option<ReExport>,
+        span: Option<Span>,
+    ) -> Self {
+        let rendered = tokens_to_string(&tokens);
+        Self {
+            path,
+            tokens,
+            reexport,
+            span,
+            rendered,
+        }
+    }
+
+    /// The path the item can be used at, starting with the crate name, e.g.
+    /// `["your_crate", "mod_a", "function"]`.
+    #[must_use]
+    pub fn path(&self) -> &[String] {
+        &self.path
+    }
+
     /// The rendered item as a stream of [`Token`]s
     pub fn tokens(&self) -> impl Iterator<Item = &Token> {
         self.tokens.iter()
     }
+
+    /// If the item is re-exported, e.g. with `pub use other_crate::Thing`,
+    /// returns where the item is originally defined.
+    #[must_use]
+    pub fn reexport(&self) -> Option<&ReExport> {
+        self.reexport.as_ref()
+    }
+
+    /// Where in the source code the item is defined, if rustdoc knows. For
+    /// re-exported items, this is where the original item is defined.
+    #[must_use]
+    pub fn span(&self) -> Option<&Span> {
+        self.span.as_ref()
+    }
+}
+
+impl PartialEq for PublicItem {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.tokens == other.tokens
+    }
+}
+
+impl Eq for PublicItem {}
+
+impl std::hash::Hash for PublicItem {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.tokens.hash(state);
+    }
+}
+
+/// Where a re-exported [`PublicItem`] is originally defined. Items are
+/// re-exported with e.g. `pub use other_crate::Thing as Alias`, or by being
+/// defined in a private module and re-exported from a public one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ReExport {
+    /// The path the item is defined at, starting with the crate name.
+    pub(crate) original_path: Vec<String>,
+
+    /// Whether or not the item is defined in another crate.
+    pub(crate) external: bool,
+}
+
+impl ReExport {
+    /// The path the item is defined at, e.g. `["other_crate", "mod", "Thing"]`.
+    #[must_use]
+    pub fn original_path(&self) -> &[String] {
+        &self.original_path
+    }
+
+    /// The name of the crate the item is defined in.
+    #[must_use]
+    pub fn crate_name(&self) -> &str {
+        self.original_path.first().map_or("", String::as_str)
+    }
+
+    /// Whether or not the item is defined in another crate than the one the
+    /// public API is for, i.e. if a dependency is part of the public API.
+    #[must_use]
+    pub fn is_external(&self) -> bool {
+        self.external
+    }
+}
+
+/// Where in the source code a [`PublicItem`] is defined. Implements [`Display`]
+/// as e.g. `src/lib.rs:42`, which many editors and terminals know how to open.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Span {
+    /// The path to the source file, as given to rustdoc.
+    pub(crate) filename: PathBuf,
+
+    /// The line and column where the item begins.
+    pub(crate) begin: (usize, usize),
+
+    /// The line and column where the item ends.
+    pub(crate) end: (usize, usize),
+}
+
+impl Span {
+    /// The path to the source file, typically relative to the directory cargo
+    /// was run in, e.g. `src/lib.rs`.
+    #[must_use]
+    pub fn filename(&self) -> &Path {
+        &self.filename
+    }
+
+    /// The line (1-based) and column (0-based) where the item begins.
+    #[must_use]
+    pub fn begin(&self) -> (usize, usize) {
+        self.begin
+    }
+
+    /// The line (1-based) and column (0-based) where the item ends.
+    #[must_use]
+    pub fn end(&self) -> (usize, usize) {
+        self.end
+    }
+}
+
+impl Display for Span {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.filename.display(), self.begin.0)
+    }
 }
 
 /// We want pretty-printing (`"{:#?}"`) of [`crate::diff::PublicItemsDiff`] to print
@@ -390,7 +700,7 @@ impl std::fmt::Debug for PublicItem {
 /// we implement `Display` for it.
 impl Display for PublicItem {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", tokens_to_string(&self.tokens))
+        f.write_str(&self.rendered)
     }
 }
 
@@ -406,6 +716,6 @@ impl PartialOrd for PublicItem {
 
 impl Ord for PublicItem {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.to_string().cmp(&other.to_string())
+        self.rendered.cmp(&other.rendered)
     }
 }
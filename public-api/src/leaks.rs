@@ -0,0 +1,385 @@
+//! Finds types from other crates that are part of the public API of a crate,
+//! for example `bytes::Bytes` in `pub fn f() -> bytes::Bytes`. Such types tie
+//! the semver of your crate to the semver of the crate they come from: when
+//! the other crate makes a breaking release that you upgrade to, your public
+//! API is broken too.
+//!
+//! Use [`leaked_types_from_rustdoc_json_str`] to find them.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use rustdoc_types::{
+    Crate, FnDecl, GenericArg, GenericArgs, GenericBound, GenericParamDef, GenericParamDefKind,
+    Generics, Id, Import, Item, ItemEnum, ItemKind, Path, Term, Type, TypeBindingKind,
+    WherePredicate,
+};
+
+use crate::{
+    format_versions, intermediate_public_item::IntermediatePublicItem, item_iterator::ItemIterator,
+    Options, Result,
+};
+
+/// Crates that are part of the Rust distribution. Types from these crates are
+/// not considered to be leaked, since they never make breaking releases.
+const STANDARD_LIBRARY_CRATES: &[&str] = &["alloc", "core", "proc_macro", "std", "test"];
+
+/// All types from other crates that are part of a public API, grouped by the
+/// crate they come from. Return type of [`leaked_types_from_rustdoc_json_str`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakedTypes {
+    /// The crates that types are leaked from, sorted by crate name.
+    pub crates: Vec<LeakingCrate>,
+}
+
+/// A crate that one or more types of the public API come from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakingCrate {
+    /// The name of the crate, e.g. `bytes`.
+    pub name: String,
+
+    /// The types from the crate that are part of the public API, sorted by
+    /// path.
+    pub types: Vec<LeakedType>,
+}
+
+/// A type from another crate that is part of the public API.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeakedType {
+    /// The path of the type in the crate it is defined in, e.g.
+    /// `bytes::bytes::Bytes`.
+    pub path: String,
+
+    /// The public items whose signature contain the type, e.g.
+    /// `your_crate::f`. Sorted.
+    pub used_by: Vec<String>,
+}
+
+impl LeakedTypes {
+    /// Returns `true` if no types from other crates are part of the public API.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.crates.is_empty()
+    }
+
+    /// Returns the crates that types are leaked from, except the crates
+    /// in `allowlist`. Handy to e.g. fail CI if types from a new crate become
+    /// part of the public API.
+    #[must_use]
+    pub fn not_allowed(&self, allowlist: &[impl AsRef<str>]) -> Vec<&LeakingCrate> {
+        self.crates
+            .iter()
+            .filter(|c| !allowlist.iter().any(|allowed| allowed.as_ref() == c.name))
+            .collect()
+    }
+}
+
+/// Takes rustdoc JSON and returns all types from other crates (except the
+/// standard library) that are part of the public API of the crate. A type is
+/// part of the public API if it is re-exported, or if it appears in the
+/// signature of a public item. See [`crate::public_api_from_rustdoc_json_str`]
+/// for how to build the rustdoc JSON.
+///
+/// # Errors
+///
+/// E.g. if the JSON is invalid.
+pub fn leaked_types_from_rustdoc_json_str(
+    rustdoc_json_str: &str,
+    options: Options,
+) -> Result<LeakedTypes> {
+    let crate_ = format_versions::deserialize_crate(rustdoc_json_str.as_bytes())?;
+    Ok(leaked_types_in_crate(&crate_, options))
+}
+
+fn leaked_types_in_crate(crate_: &Crate, options: Options) -> LeakedTypes {
+    // crate name -> type path -> public items using the type
+    let mut leaks: BTreeMap<String, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+
+    for public_item in ItemIterator::new(crate_, options) {
+        let mut leaked = vec![];
+
+        if let Some(reexport) = public_item
+            .reexport
+            .as_ref()
+            .filter(|r| r.is_external() && !is_module(crate_, public_item.item))
+        {
+            leaked.push((
+                reexport.crate_name().to_owned(),
+                reexport.original_path().join("::"),
+            ));
+        }
+
+        let mut ids = vec![];
+        ids_in_item(&public_item, &mut ids);
+        leaked.extend(ids.into_iter().filter_map(|id| external_type(crate_, id)));
+
+        let used_by = public_item
+            .path()
+            .iter()
+            .map(|p| p.name.as_ref())
+            .collect::<Vec<_>>()
+            .join("::");
+        for (crate_name, type_path) in leaked {
+            if STANDARD_LIBRARY_CRATES.contains(&crate_name.as_str()) {
+                continue;
+            }
+            leaks
+                .entry(crate_name)
+                .or_default()
+                .entry(type_path)
+                .or_default()
+                .insert(used_by.clone());
+        }
+    }
+
+    LeakedTypes {
+        crates: leaks
+            .into_iter()
+            .map(|(name, types)| LeakingCrate {
+                name,
+                types: types
+                    .into_iter()
+                    .map(|(path, used_by)| LeakedType {
+                        path,
+                        used_by: used_by.into_iter().collect(),
+                    })
+                    .collect(),
+            })
+            .collect(),
+    }
+}
+
+/// Returns `true` if `item` is a module or an extern crate, or an import of
+/// one. Re-exporting those is not the same as leaking a type, since it is the
+/// items inside them that are used.
+fn is_module(crate_: &Crate, item: &Item) -> bool {
+    match &item.inner {
+        ItemEnum::Module(_) | ItemEnum::ExternCrate { .. } => true,
+        ItemEnum::Import(Import {
+            id: Some(imported_id),
+            ..
+        }) => crate_.paths.get(imported_id).map_or(false, |summary| {
+            matches!(summary.kind, ItemKind::Module | ItemKind::ExternCrate)
+        }),
+        _ => false,
+    }
+}
+
+/// If `id` refers to an item in another crate, returns the name of that crate
+/// and the path of the item.
+fn external_type(crate_: &Crate, id: &Id) -> Option<(String, String)> {
+    let summary = crate_.paths.get(id)?;
+    if summary.crate_id == 0 {
+        return None;
+    }
+
+    let crate_name = crate_.external_crates.get(&summary.crate_id).map_or_else(
+        || summary.path.first().cloned().unwrap_or_default(),
+        |c| c.name.clone(),
+    );
+    Some((crate_name, summary.path.join("::")))
+}
+
+/// Collects the Ids of all paths (types and traits) in the signature of an
+/// item. Children of the item, such as struct fields, are items of their own,
+/// so they are not included.
+fn ids_in_item<'a>(public_item: &IntermediatePublicItem<'a>, ids: &mut Vec<&'a Id>) {
+    for type_ in public_item.pre_resolved_fields.iter().flatten() {
+        ids_in_type(type_, ids);
+    }
+
+    match &public_item.item.inner {
+        ItemEnum::Union(u) => ids_in_generics(&u.generics, ids),
+        ItemEnum::Struct(s) => ids_in_generics(&s.generics, ids),
+        ItemEnum::Enum(e) => ids_in_generics(&e.generics, ids),
+        ItemEnum::StructField(type_)
+        | ItemEnum::AssocConst { type_, .. }
+        | ItemEnum::Constant(rustdoc_types::Constant { type_, .. })
+        | ItemEnum::Static(rustdoc_types::Static { type_, .. }) => ids_in_type(type_, ids),
+        ItemEnum::Function(f) => {
+            ids_in_fn_decl(&f.decl, ids);
+            ids_in_generics(&f.generics, ids);
+        }
+        ItemEnum::Method(m) => {
+            ids_in_fn_decl(&m.decl, ids);
+            ids_in_generics(&m.generics, ids);
+        }
+        ItemEnum::Trait(t) => {
+            ids_in_generics(&t.generics, ids);
+            ids_in_bounds(&t.bounds, ids);
+        }
+        ItemEnum::TraitAlias(t) => {
+            ids_in_generics(&t.generics, ids);
+            ids_in_bounds(&t.params, ids);
+        }
+        ItemEnum::Typedef(t) => {
+            ids_in_type(&t.type_, ids);
+            ids_in_generics(&t.generics, ids);
+        }
+        ItemEnum::OpaqueTy(o) => {
+            ids_in_bounds(&o.bounds, ids);
+            ids_in_generics(&o.generics, ids);
+        }
+        ItemEnum::AssocType {
+            generics,
+            bounds,
+            default,
+        } => {
+            ids_in_generics(generics, ids);
+            ids_in_bounds(bounds, ids);
+            if let Some(default) = default {
+                ids_in_type(default, ids);
+            }
+        }
+        ItemEnum::Module(_)
+        | ItemEnum::ExternCrate { .. }
+        | ItemEnum::Import(_)
+        | ItemEnum::Variant(_)
+        | ItemEnum::Impl(_)
+        | ItemEnum::ForeignType
+        | ItemEnum::Macro(_)
+        | ItemEnum::ProcMacro(_)
+        | ItemEnum::PrimitiveType(_) => {}
+    }
+}
+
+fn ids_in_type<'a>(type_: &'a Type, ids: &mut Vec<&'a Id>) {
+    match type_ {
+        Type::ResolvedPath(path) => ids_in_path(path, ids),
+        Type::DynTrait(dyn_trait) => {
+            for poly_trait in &dyn_trait.traits {
+                ids_in_path(&poly_trait.trait_, ids);
+                ids_in_generic_param_defs(&poly_trait.generic_params, ids);
+            }
+        }
+        Type::FunctionPointer(fp) => {
+            ids_in_fn_decl(&fp.decl, ids);
+            ids_in_generic_param_defs(&fp.generic_params, ids);
+        }
+        Type::Tuple(elements) => {
+            for element in elements {
+                ids_in_type(element, ids);
+            }
+        }
+        Type::Slice(type_)
+        | Type::Array { type_, .. }
+        | Type::RawPointer { type_, .. }
+        | Type::BorrowedRef { type_, .. } => ids_in_type(type_, ids),
+        Type::ImplTrait(bounds) => ids_in_bounds(bounds, ids),
+        Type::QualifiedPath {
+            args,
+            self_type,
+            trait_,
+            ..
+        } => {
+            ids_in_generic_args(args, ids);
+            ids_in_type(self_type, ids);
+            ids_in_path(trait_, ids);
+        }
+        Type::Generic(_) | Type::Primitive(_) | Type::Infer => {}
+    }
+}
+
+fn ids_in_path<'a>(path: &'a Path, ids: &mut Vec<&'a Id>) {
+    ids.push(&path.id);
+    if let Some(args) = &path.args {
+        ids_in_generic_args(args, ids);
+    }
+}
+
+fn ids_in_generic_args<'a>(args: &'a GenericArgs, ids: &mut Vec<&'a Id>) {
+    match args {
+        GenericArgs::AngleBracketed { args, bindings } => {
+            for arg in args {
+                match arg {
+                    GenericArg::Type(type_) => ids_in_type(type_, ids),
+                    GenericArg::Const(constant) => ids_in_type(&constant.type_, ids),
+                    GenericArg::Lifetime(_) | GenericArg::Infer => {}
+                }
+            }
+            for binding in bindings {
+                ids_in_generic_args(&binding.args, ids);
+                match &binding.binding {
+                    TypeBindingKind::Equality(term) => ids_in_term(term, ids),
+                    TypeBindingKind::Constraint(bounds) => ids_in_bounds(bounds, ids),
+                }
+            }
+        }
+        GenericArgs::Parenthesized { inputs, output } => {
+            for input in inputs {
+                ids_in_type(input, ids);
+            }
+            if let Some(output) = output {
+                ids_in_type(output, ids);
+            }
+        }
+    }
+}
+
+fn ids_in_term<'a>(term: &'a Term, ids: &mut Vec<&'a Id>) {
+    match term {
+        Term::Type(type_) => ids_in_type(type_, ids),
+        Term::Constant(constant) => ids_in_type(&constant.type_, ids),
+    }
+}
+
+fn ids_in_fn_decl<'a>(decl: &'a FnDecl, ids: &mut Vec<&'a Id>) {
+    for (_, type_) in &decl.inputs {
+        ids_in_type(type_, ids);
+    }
+    if let Some(output) = &decl.output {
+        ids_in_type(output, ids);
+    }
+}
+
+fn ids_in_generics<'a>(generics: &'a Generics, ids: &mut Vec<&'a Id>) {
+    ids_in_generic_param_defs(&generics.params, ids);
+    for predicate in &generics.where_predicates {
+        match predicate {
+            WherePredicate::BoundPredicate {
+                type_,
+                bounds,
+                generic_params,
+            } => {
+                ids_in_type(type_, ids);
+                ids_in_bounds(bounds, ids);
+                ids_in_generic_param_defs(generic_params, ids);
+            }
+            WherePredicate::RegionPredicate { bounds, .. } => ids_in_bounds(bounds, ids),
+            WherePredicate::EqPredicate { lhs, rhs } => {
+                ids_in_type(lhs, ids);
+                ids_in_term(rhs, ids);
+            }
+        }
+    }
+}
+
+fn ids_in_generic_param_defs<'a>(params: &'a [GenericParamDef], ids: &mut Vec<&'a Id>) {
+    for param in params {
+        match &param.kind {
+            GenericParamDefKind::Type {
+                bounds, default, ..
+            } => {
+                ids_in_bounds(bounds, ids);
+                if let Some(default) = default {
+                    ids_in_type(default, ids);
+                }
+            }
+            GenericParamDefKind::Const { type_, .. } => ids_in_type(type_, ids),
+            GenericParamDefKind::Lifetime { .. } => {}
+        }
+    }
+}
+
+fn ids_in_bounds<'a>(bounds: &'a [GenericBound], ids: &mut Vec<&'a Id>) {
+    for bound in bounds {
+        if let GenericBound::TraitBound {
+            trait_,
+            generic_params,
+            ..
+        } = bound
+        {
+            ids_in_path(trait_, ids);
+            ids_in_generic_param_defs(generic_params, ids);
+        }
+    }
+}
@@ -15,7 +15,16 @@
 //! ```
 //!
 //! The main entry point to the library is [`public_api_from_rustdoc_json_str`],
-//! so please read its documentation.
+//! so please read its documentation. For huge rustdoc JSON files, there are
+//! also [`public_api_from_rustdoc_json_path`] and
+//! [`public_api_from_rustdoc_json_reader`], which need a lot less memory.
+//!
+//! With the `build` cargo feature enabled, `PublicApi::builder()` can also
+//! build the rustdoc JSON for you, so that you get the public API of a crate
+//! straight from its `Cargo.toml`.
+//!
+//! To find types from other crates that are part of the public API, see the
+//! [`leaks`] module.
 //!
 //! # Examples
 //!
@@ -39,19 +48,36 @@
 // deny in CI, only warn here
 #![warn(clippy::all, clippy::pedantic, missing_docs)]
 
+use std::{io::Read, path::Path};
+
+use rustdoc_types::Crate;
+
+#[cfg(feature = "build")]
+mod builder;
+mod diagnostic;
 mod error;
+mod format_versions;
 mod intermediate_public_item;
 mod item_iterator;
+mod parallel;
 mod render;
 pub mod tokens;
 
 pub mod diff;
+pub mod leaks;
+
+// Documented at the definition site so cargo doc picks it up
+pub use diagnostic::{Diagnostic, DiagnosticKind};
 
 // Documented at the definition site so cargo doc picks it up
 pub use error::{Error, Result};
 
 // Documented at the definition site so cargo doc picks it up
-pub use item_iterator::PublicItem;
+pub use item_iterator::{PublicItem, ReExport, Span};
+
+// Documented at the definition site so cargo doc picks it up
+#[cfg(feature = "build")]
+pub use builder::PublicApiBuilder;
 
 /// This constant defines the minimum version of nightly that is required in
 /// order for the rustdoc JSON output to be parsable by this library. Note that
@@ -84,6 +110,14 @@ pub struct Options {
     /// The default value is `true`, because usually the performance impact is
     /// negligible, and is is generally more practical to work with sorted data.
     pub sorted: bool,
+
+    /// If `true`, every re-exported item is accompanied by an item such as
+    /// `pub use other_crate::Thing as your_crate::Alias` that shows where the
+    /// re-exported item is defined. Handy to see what parts of the public API
+    /// come from dependencies. See also [`PublicItem::reexport`].
+    ///
+    /// The default value is `false`.
+    pub with_reexports: bool,
 }
 
 /// Enables options to be set up like this (note that `Options` is marked
@@ -100,6 +134,7 @@ impl Default for Options {
         Self {
             with_blanket_implementations: false,
             sorted: true,
+            with_reexports: false,
         }
     }
 }
@@ -123,6 +158,9 @@ impl Default for Options {
 /// still a moving target. Open PRs and issues for rustdoc JSON itself can be
 /// found at <https://github.com/rust-lang/rust/labels/A-rustdoc-json>.
 ///
+/// If the rustdoc JSON is in a file, [`public_api_from_rustdoc_json_path`] can
+/// read it for you.
+///
 /// # Errors
 ///
 /// E.g. if the JSON is invalid.
@@ -130,15 +168,111 @@ pub fn public_api_from_rustdoc_json_str(
     rustdoc_json_str: &str,
     options: Options,
 ) -> Result<PublicApi> {
-    let crate_ = deserialize_without_recursion_limit(rustdoc_json_str)?;
+    let crate_ = format_versions::deserialize_crate(rustdoc_json_str.as_bytes())?;
+
+    Ok(public_api_from_crate(&crate_, options))
+}
+
+/// Same as [`public_api_from_rustdoc_json_str`], but reads the rustdoc JSON
+/// from the file at `rustdoc_json_path` for you. For huge rustdoc JSON files,
+/// such as the ones of the `windows` crate, see
+/// `public_api_from_rustdoc_json_mmap()` for a way to lower peak memory usage.
+/// It requires the `mmap` cargo feature.
+///
+/// # Errors
+///
+/// E.g. if the file can't be read or if the JSON is invalid.
+pub fn public_api_from_rustdoc_json_path(
+    rustdoc_json_path: impl AsRef<Path>,
+    options: Options,
+) -> Result<PublicApi> {
+    let rustdoc_json = std::fs::read(rustdoc_json_path)?;
+    let crate_ = format_versions::deserialize_crate(&rustdoc_json)?;
 
-    let mut public_api = item_iterator::public_api_in_crate(&crate_, options);
+    Ok(public_api_from_crate(&crate_, options))
+}
+
+/// Same as [`public_api_from_rustdoc_json_path`], but memory-maps the rustdoc
+/// JSON file instead of reading it into memory. For huge rustdoc JSON files,
+/// this considerably lowers peak memory usage, since the operating system can
+/// page the file contents in and out as needed.
+///
+/// # Safety
+///
+/// The file at `rustdoc_json_path` must not be modified or truncated, by this
+/// or any other process, until this function returns. Otherwise the behavior
+/// is undefined, and the process might for example be killed by `SIGBUS`. Keep
+/// in mind that building rustdoc JSON for a crate again overwrites the file.
+///
+/// # Errors
+///
+/// E.g. if the file can't be mapped or if the JSON is invalid.
+///
+/// Requires the `mmap` cargo feature.
+#[cfg(feature = "mmap")]
+pub unsafe fn public_api_from_rustdoc_json_mmap(
+    rustdoc_json_path: impl AsRef<Path>,
+    options: Options,
+) -> Result<PublicApi> {
+    let file = std::fs::File::open(rustdoc_json_path)?;
+
+    // SAFETY: The mapping is read-only and only lives during this function,
+    // and our caller guarantees that the file is not modified meanwhile.
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    let crate_ = format_versions::deserialize_crate(&mmap)?;
+
+    Ok(public_api_from_crate(&crate_, options))
+}
+
+/// Same as [`public_api_from_rustdoc_json_path`], but for many rustdoc JSON
+/// files at once. The files are processed concurrently on a pool of threads,
+/// one thread per available CPU core. Handy when you e.g. want the public API
+/// of every crate in a workspace.
+///
+/// Returns one result per path, in the same order as `rustdoc_json_paths`.
+/// Keep in mind that peak memory usage grows with the number of files that
+/// are processed at the same time.
+#[must_use]
+pub fn public_api_from_rustdoc_json_paths<P: AsRef<Path> + Sync>(
+    rustdoc_json_paths: &[P],
+    options: Options,
+) -> Vec<Result<PublicApi>> {
+    parallel::map(rustdoc_json_paths, |path| {
+        public_api_from_rustdoc_json_path(path, options)
+    })
+}
+
+/// Same as [`public_api_from_rustdoc_json_str`], but deserializes the rustdoc
+/// JSON as it is read from `reader`, so that the JSON never needs to be in
+/// memory all at once. The reader is buffered internally.
+///
+/// Since rustdoc puts the format version at the end of the JSON, this function
+/// can only look at it after the fact. It therefore only supports the format
+/// version of [`rustdoc_types::FORMAT_VERSION`], regardless of what cargo
+/// features are enabled. For other format versions you get either
+/// [`Error::UnsupportedFormatVersion`] or, if the formats are too different, a
+/// deserialization error.
+///
+/// # Errors
+///
+/// E.g. if reading fails or if the JSON is invalid.
+pub fn public_api_from_rustdoc_json_reader(
+    reader: impl Read,
+    options: Options,
+) -> Result<PublicApi> {
+    let crate_ = format_versions::deserialize_crate_from_reader(reader)?;
+
+    Ok(public_api_from_crate(&crate_, options))
+}
+
+fn public_api_from_crate(crate_: &Crate, options: Options) -> PublicApi {
+    let mut public_api = item_iterator::public_api_in_crate(crate_, options);
 
     if options.sorted {
         public_api.items.sort();
     }
 
-    Ok(public_api)
+    public_api
 }
 
 /// Return type of [`public_api_from_rustdoc_json_str`].
@@ -161,14 +295,14 @@ pub struct PublicApi {
     ///
     /// The exact format of IDs are to be considered an implementation detail
     /// and must not be be relied on.
+    #[deprecated(
+        note = "use `warnings` instead, missing items have the kind `DiagnosticKind::MissingItem`"
+    )]
     pub missing_item_ids: Vec<String>,
-}
 
-/// Helper to deserialize the JSON with `serde_json`, but with the recursion
-/// limit disabled. Otherwise we hit the recursion limit on crates such as
-/// `diesel`.
-fn deserialize_without_recursion_limit(rustdoc_json_str: &str) -> Result<rustdoc_types::Crate> {
-    let mut deserializer = serde_json::Deserializer::from_str(rustdoc_json_str);
-    deserializer.disable_recursion_limit();
-    Ok(serde::de::Deserialize::deserialize(&mut deserializer)?)
+    /// Things that happened while collecting the public API that might explain
+    /// why some items are not listed, such as items missing from the rustdoc
+    /// JSON, or glob imports that could not be inlined. Intended for use with
+    /// `--verbose` flags or similar.
+    pub warnings: Vec<Diagnostic>,
 }
@@ -8,7 +8,7 @@ use std::io::{stdout, ErrorKind, Write};
 use std::path::{Path, PathBuf};
 
 use public_api::diff::PublicItemsDiff;
-use public_api::{public_api_from_rustdoc_json_str, Options, MINIMUM_RUSTDOC_JSON_VERSION};
+use public_api::{public_api_from_rustdoc_json_path, Options, MINIMUM_RUSTDOC_JSON_VERSION};
 
 #[derive(thiserror::Error, Debug)]
 enum Error {
@@ -31,7 +31,7 @@ struct Args {
 fn main_() -> Result<()> {
     let args = args();
     if args.print_minimum_rustdoc_json_version {
-        println!("{}", MINIMUM_RUSTDOC_JSON_VERSION);
+        println!("{MINIMUM_RUSTDOC_JSON_VERSION}");
         return Ok(());
     }
 
@@ -55,21 +55,16 @@ fn main_() -> Result<()> {
 }
 
 fn print_public_api(path: &Path, options: Options) -> Result<()> {
-    let json = &std::fs::read_to_string(path)?;
-
-    for public_item in public_api_from_rustdoc_json_str(json, options)?.items {
-        writeln!(std::io::stdout(), "{}", public_item)?;
+    for public_item in public_api_from_rustdoc_json_path(path, options)?.items {
+        writeln!(std::io::stdout(), "{public_item}")?;
     }
 
     Ok(())
 }
 
 fn print_public_api_diff(old: &Path, new: &Path, options: Options) -> Result<()> {
-    let old_json = std::fs::read_to_string(old)?;
-    let old = public_api_from_rustdoc_json_str(&old_json, options)?;
-
-    let new_json = std::fs::read_to_string(new)?;
-    let new = public_api_from_rustdoc_json_str(&new_json, options)?;
+    let old = public_api_from_rustdoc_json_path(old, options)?;
+    let new = public_api_from_rustdoc_json_path(new, options)?;
 
     let diff = PublicItemsDiff::between(old.items, new.items);
     print_diff_with_headers(&diff, &mut stdout(), "Removed:", "Changed:", "Added:")?;
@@ -85,14 +80,14 @@ fn print_diff_with_headers(
     header_added: &str,
 ) -> std::io::Result<()> {
     print_items_with_header(w, header_removed, &diff.removed, |w, item| {
-        writeln!(w, "-{}", item)
+        writeln!(w, "-{item}")
     })?;
     print_items_with_header(w, header_changed, &diff.changed, |w, item| {
         writeln!(w, "-{}", item.old)?;
         writeln!(w, "+{}", item.new)
     })?;
     print_items_with_header(w, header_added, &diff.added, |w, item| {
-        writeln!(w, "+{}", item)
+        writeln!(w, "+{item}")
     })?;
 
     Ok(())
@@ -104,7 +99,7 @@ fn print_items_with_header<W: std::io::Write, T>(
     items: &[T],
     print_fn: impl Fn(&mut W, &T) -> std::io::Result<()>,
 ) -> std::io::Result<()> {
-    writeln!(w, "{}", header)?;
+    writeln!(w, "{header}")?;
     if items.is_empty() {
         writeln!(w, "(nothing)")?;
     } else {
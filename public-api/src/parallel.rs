@@ -0,0 +1,38 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Applies `f` to each of `inputs` on a pool of threads, one per available CPU
+/// core, and returns the outputs in the same order as the inputs. Each thread
+/// takes the next unprocessed input until there are none left, so that a few
+/// slow inputs do not hold up the rest.
+pub(crate) fn map<T: Sync, R: Send>(inputs: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, NonZeroUsize::get)
+        .min(inputs.len());
+
+    let next_input = AtomicUsize::new(0);
+    let outputs: Mutex<Vec<Option<R>>> = Mutex::new(inputs.iter().map(|_| None).collect());
+
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| loop {
+                let index = next_input.fetch_add(1, Ordering::Relaxed);
+                let input = match inputs.get(index) {
+                    Some(input) => input,
+                    None => break,
+                };
+
+                let output = f(input);
+                outputs.lock().expect("no thread panics with the lock held")[index] = Some(output);
+            });
+        }
+    });
+
+    outputs
+        .into_inner()
+        .expect("no thread panics with the lock held")
+        .into_iter()
+        .map(|output| output.expect("all inputs have been processed"))
+        .collect()
+}
@@ -1,10 +1,10 @@
-use crate::intermediate_public_item::IntermediatePublicItem;
-use std::rc::Rc;
+use crate::intermediate_public_item::{IntermediatePublicItem, PathComponent};
+use crate::ReExport;
 
 use rustdoc_types::{
     Abi, Constant, FnDecl, FunctionPointer, GenericArg, GenericArgs, GenericBound, GenericParamDef,
     GenericParamDefKind, Generics, Header, ItemEnum, MacroKind, Path, PolyTrait, StructKind, Term,
-    Type, TypeBinding, TypeBindingKind, Variant, WherePredicate,
+    TraitBoundModifier, Type, TypeBinding, TypeBindingKind, Variant, WherePredicate,
 };
 
 /// A simple macro to write `Token::Whitespace` in less characters.
@@ -27,37 +27,68 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
         }
     }
 
+    let path = item.path();
     let inner_tokens = match &item.item.inner {
-        ItemEnum::Module(_) => render_simple(&["mod"], &item.path()),
-        ItemEnum::ExternCrate { .. } => render_simple(&["extern", "crate"], &item.path()),
-        ItemEnum::Import(_) => render_simple(&["use"], &item.path()),
-        ItemEnum::Union(_) => render_simple(&["union"], &item.path()),
+        ItemEnum::Module(_) => render_simple(&["mod"], path),
+        ItemEnum::ExternCrate { .. } => render_simple(&["extern", "crate"], path),
+        ItemEnum::Import(_) => render_simple(&["use"], path),
+        ItemEnum::Union(u) => {
+            let mut output = render_simple(&["union"], path);
+            output.extend(render_generics(&u.generics));
+            if u.fields_stripped {
+                output.extend(render_stripped("private fields"));
+            }
+            output
+        }
         ItemEnum::Struct(s) => {
-            let mut output = render_simple(&["struct"], &item.path());
+            let mut output = render_simple(&["struct"], path);
             output.extend(render_generics(&s.generics));
-            if matches!(s.kind, StructKind::Tuple(_)) {
-                output.extend(render_option_tuple(
+            match s.kind {
+                StructKind::Tuple(_) => output.extend(render_option_tuple(
                     &item.pre_resolved_fields,
                     Some(&pub_()),
-                ));
+                )),
+                StructKind::Plain {
+                    fields_stripped: true,
+                    ..
+                } => output.extend(render_stripped("private fields")),
+                StructKind::Plain { .. } | StructKind::Unit => {}
             }
             output
         }
         ItemEnum::StructField(inner) => {
-            let mut output = render_simple(&["struct", "field"], &item.path());
+            let in_union = path.iter().rev().nth(1).map_or(false, |parent| {
+                matches!(parent.item.inner, ItemEnum::Union(_))
+            });
+            let tags = if in_union {
+                ["union", "field"]
+            } else {
+                ["struct", "field"]
+            };
+            let mut output = render_simple(&tags, path);
             output.extend(colon());
             output.extend(render_type(inner));
             output
         }
         ItemEnum::Enum(e) => {
-            let mut output = render_simple(&["enum"], &item.path());
+            let mut output = render_simple(&["enum"], path);
             output.extend(render_generics(&e.generics));
+            if e.variants_stripped {
+                output.extend(render_stripped("some variants omitted"));
+            }
             output
         }
         ItemEnum::Variant(inner) => {
-            let mut output = render_simple(&["enum", "variant"], &item.path());
+            let mut output = render_simple(&["enum", "variant"], path);
             match inner {
-                Variant::Struct { .. } => {} // Each struct field is printed individually
+                // Each struct field is printed individually
+                Variant::Struct {
+                    fields_stripped, ..
+                } => {
+                    if *fields_stripped {
+                        output.extend(render_stripped("private fields"));
+                    }
+                }
                 Variant::Plain(discriminant) => {
                     if let Some(discriminant) = discriminant {
                         output.extend(equals());
@@ -71,13 +102,13 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
             output
         }
         ItemEnum::Function(inner) => render_function(
-            render_path(&item.path()),
+            render_path(path),
             &inner.decl,
             &inner.generics,
             &inner.header,
         ),
         ItemEnum::Method(inner) => render_function(
-            render_path(&item.path()),
+            render_path(path),
             &inner.decl,
             &inner.generics,
             &inner.header,
@@ -88,14 +119,14 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
             } else {
                 vec!["trait"]
             };
-            let mut output = render_simple(&tags, &item.path());
+            let mut output = render_simple(&tags, path);
             output.extend(render_generics(&inner.generics));
             output
         }
-        ItemEnum::TraitAlias(_) => render_simple(&["trait", "alias"], &item.path()),
-        ItemEnum::Impl(_) => render_simple(&["impl"], &item.path()),
+        ItemEnum::TraitAlias(_) => render_simple(&["trait", "alias"], path),
+        ItemEnum::Impl(_) => render_simple(&["impl"], path),
         ItemEnum::Typedef(inner) => {
-            let mut output = render_simple(&["type"], &item.path());
+            let mut output = render_simple(&["type"], path);
             output.extend(render_generics(&inner.generics));
             output.extend(equals());
             output.extend(render_type(&inner.type_));
@@ -106,24 +137,38 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
             bounds,
             default,
         } => {
-            let mut output = render_simple(&["type"], &item.path());
-            output.extend(render_generics(generics));
-            output.extend(render_generic_bounds(bounds));
+            let mut output = render_simple(&["type"], path);
+            output.extend(render_generic_param_defs(&generics.params));
+            if !bounds.is_empty() {
+                output.extend(colon());
+                output.extend(render_generic_bounds(bounds));
+            }
+            output.extend(render_where_predicates(&generics.where_predicates));
             if let Some(ty) = default {
                 output.extend(equals());
                 output.extend(render_type(ty));
             }
             output
         }
-        ItemEnum::OpaqueTy(_) => render_simple(&["opaque", "type"], &item.path()),
+        ItemEnum::OpaqueTy(opaque_ty) => {
+            let mut output = render_simple(&["type"], path);
+            output.extend(render_generic_param_defs(&opaque_ty.generics.params));
+            output.extend(equals());
+            output.extend(vec![Token::keyword("impl"), ws!()]);
+            output.extend(render_generic_bounds(&opaque_ty.bounds));
+            output.extend(render_where_predicates(
+                &opaque_ty.generics.where_predicates,
+            ));
+            output
+        }
         ItemEnum::Constant(con) => {
-            let mut output = render_simple(&["const"], &item.path());
+            let mut output = render_simple(&["const"], path);
             output.extend(colon());
             output.extend(render_constant(con));
             output
         }
         ItemEnum::AssocConst { type_, .. } => {
-            let mut output = render_simple(&["const"], &item.path());
+            let mut output = render_simple(&["const"], path);
             output.extend(colon());
             output.extend(render_type(type_));
             output
@@ -134,22 +179,22 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
             } else {
                 vec!["static"]
             };
-            let mut output = render_simple(&tags, &item.path());
+            let mut output = render_simple(&tags, path);
             output.extend(colon());
             output.extend(render_type(&inner.type_));
             output
         }
-        ItemEnum::ForeignType => render_simple(&["type"], &item.path()),
+        ItemEnum::ForeignType => render_simple(&["type"], path),
         ItemEnum::Macro(_definition) => {
             // TODO: _definition contains the whole definition, it would be really neat to get out all possible ways to invoke it
-            let mut output = render_simple(&["macro"], &item.path());
+            let mut output = render_simple(&["macro"], path);
             output.push(Token::symbol("!"));
             output
         }
         ItemEnum::ProcMacro(inner) => {
-            let mut output = render_simple(&["proc", "macro"], &item.path());
+            let mut output = render_simple(&["proc", "macro"], path);
             output.pop(); // Remove name of macro to possibly wrap it in `#[]`
-            let name = Token::identifier(item.item.name.as_ref().unwrap_or(&"".to_string()));
+            let name = Token::identifier(item.item.name.as_ref().unwrap_or(&String::new()));
             match inner.kind {
                 MacroKind::Bang => output.extend(vec![name, Token::symbol("!()")]),
                 MacroKind::Attr => {
@@ -157,11 +202,12 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
                 }
                 MacroKind::Derive => {
                     output.extend(vec![Token::symbol("#[derive("), name, Token::symbol(")]")]);
+                    output.extend(render_derive_helpers(&inner.helpers));
                 }
             }
             output
         }
-        ItemEnum::PrimitiveType(_) => render_simple(&["primitive", "type"], &item.path()),
+        ItemEnum::PrimitiveType(_) => render_simple(&["primitive", "type"], path),
     };
 
     tokens.extend(inner_tokens);
@@ -169,7 +215,54 @@ pub fn token_stream(item: &IntermediatePublicItem) -> Vec<Token> {
     tokens
 }
 
-fn render_simple(tags: &[&str], path: &[Rc<IntermediatePublicItem<'_>>]) -> Vec<Token> {
+/// Renders a re-exported item as e.g. `pub use other_crate::Thing as
+/// your_crate::Alias`, i.e. where it is defined and where it is available.
+pub fn reexport_token_stream(item: &IntermediatePublicItem, reexport: &ReExport) -> Vec<Token> {
+    let mut output = pub_();
+    output.extend(vec![Token::kind("use"), ws!()]);
+    for segment in reexport.original_path() {
+        output.push(Token::identifier(segment));
+        output.push(Token::symbol("::"));
+    }
+    output.pop();
+    output.extend(vec![ws!(), Token::keyword("as"), ws!()]);
+    output.extend(render_path(item.path()));
+    output
+}
+
+/// Renders the helper attributes of a derive macro, e.g. ` helpers: #[foo],
+/// #[bar]`. Nothing is rendered if there are no helpers.
+fn render_derive_helpers(helpers: &[String]) -> Vec<Token> {
+    render_sequence_if_not_empty(
+        vec![ws!(), Token::kind("helpers"), Token::symbol(":"), ws!()],
+        vec![],
+        comma(),
+        helpers,
+        |helper| {
+            vec![
+                Token::symbol("#["),
+                Token::identifier(helper),
+                Token::symbol("]"),
+            ]
+        },
+    )
+}
+
+/// Renders a marker for stripped fields or variants, e.g. ` { /* private
+/// fields */ }`, so that it is visible in the public API that e.g. a struct
+/// can not be constructed with a struct literal.
+fn render_stripped(what: &str) -> Vec<Token> {
+    vec![
+        ws!(),
+        Token::symbol("{"),
+        ws!(),
+        Token::symbol(format!("/* {what} */")),
+        ws!(),
+        Token::symbol("}"),
+    ]
+}
+
+fn render_simple(tags: &[&str], path: &[PathComponent<'_>]) -> Vec<Token> {
     let mut output = pub_();
     output.extend(
         tags.iter()
@@ -180,7 +273,7 @@ fn render_simple(tags: &[&str], path: &[Rc<IntermediatePublicItem<'_>>]) -> Vec<
     output
 }
 
-fn render_path(path: &[Rc<IntermediatePublicItem<'_>>]) -> Vec<Token> {
+fn render_path(path: &[PathComponent<'_>]) -> Vec<Token> {
     let mut output = vec![];
     for item in path {
         let token_fn = if matches!(item.item.inner, ItemEnum::Function(_) | ItemEnum::Method(_)) {
@@ -197,7 +290,7 @@ fn render_path(path: &[Rc<IntermediatePublicItem<'_>>]) -> Vec<Token> {
         } else {
             Token::identifier
         };
-        output.push(token_fn(item.name.clone()));
+        output.push(token_fn(item.name.as_ref()));
         output.push(Token::symbol("::"));
     }
     if !path.is_empty() {
@@ -280,10 +373,10 @@ fn render_option_type(ty: &Option<&Type>) -> Vec<Token> {
         } => render_borrowed_ref(lifetime.as_deref(), *mutable, type_),
         Type::QualifiedPath {
             name,
-            args: _,
+            args,
             self_type,
             trait_,
-        } => render_qualified_path(self_type, trait_, name),
+        } => render_qualified_path(self_type, trait_, name, args),
     }
 }
 
@@ -322,31 +415,7 @@ fn render_function(
     header: &Header,
 ) -> Vec<Token> {
     let mut output = pub_();
-    if header.unsafe_ {
-        output.extend(vec![Token::qualifier("unsafe"), ws!()]);
-    };
-    if header.const_ {
-        output.extend(vec![Token::qualifier("const"), ws!()]);
-    };
-    if header.async_ {
-        output.extend(vec![Token::qualifier("async"), ws!()]);
-    };
-    if header.abi != Abi::Rust {
-        output.push(match &header.abi {
-            Abi::C { .. } => Token::qualifier("c"),
-            Abi::Cdecl { .. } => Token::qualifier("cdecl"),
-            Abi::Stdcall { .. } => Token::qualifier("stdcall"),
-            Abi::Fastcall { .. } => Token::qualifier("fastcall"),
-            Abi::Aapcs { .. } => Token::qualifier("aapcs"),
-            Abi::Win64 { .. } => Token::qualifier("win64"),
-            Abi::SysV64 { .. } => Token::qualifier("sysV64"),
-            Abi::System { .. } => Token::qualifier("system"),
-            Abi::Other(text) => Token::qualifier(text),
-            Abi::Rust => unreachable!(),
-        });
-        output.push(ws!());
-    }
-
+    output.extend(render_function_header(header));
     output.extend(vec![Token::kind("fn"), ws!()]);
     output.extend(name);
 
@@ -362,6 +431,50 @@ fn render_function(
     output
 }
 
+/// Renders the qualifiers of a function or function pointer in the order Rust
+/// requires, e.g. `const unsafe extern "C" `.
+fn render_function_header(header: &Header) -> Vec<Token> {
+    let mut output = vec![];
+    if header.const_ {
+        output.extend(vec![Token::qualifier("const"), ws!()]);
+    }
+    if header.async_ {
+        output.extend(vec![Token::qualifier("async"), ws!()]);
+    }
+    if header.unsafe_ {
+        output.extend(vec![Token::qualifier("unsafe"), ws!()]);
+    }
+    if let Some(abi) = render_abi(&header.abi) {
+        output.extend(vec![
+            Token::qualifier("extern"),
+            ws!(),
+            Token::symbol(abi),
+            ws!(),
+        ]);
+    }
+    output
+}
+
+/// Renders an ABI as a string literal for use after `extern`, e.g. `"C"` or
+/// `"C-unwind"`. Returns `None` for the default Rust ABI.
+fn render_abi(abi: &Abi) -> Option<String> {
+    let (name, unwind) = match abi {
+        Abi::Rust => return None,
+        Abi::C { unwind } => ("C", *unwind),
+        Abi::Cdecl { unwind } => ("cdecl", *unwind),
+        Abi::Stdcall { unwind } => ("stdcall", *unwind),
+        Abi::Fastcall { unwind } => ("fastcall", *unwind),
+        Abi::Aapcs { unwind } => ("aapcs", *unwind),
+        Abi::Win64 { unwind } => ("win64", *unwind),
+        Abi::SysV64 { unwind } => ("sysv64", *unwind),
+        Abi::System { unwind } => ("system", *unwind),
+        // Already the name as written in the source, possibly quoted
+        Abi::Other(name) => (name.trim_matches('"'), false),
+    };
+    let unwind = if unwind { "-unwind" } else { "" };
+    Some(format!("\"{name}{unwind}\""))
+}
+
 fn render_fn_decl(decl: &FnDecl) -> Vec<Token> {
     let mut output = vec![];
     // Main arguments
@@ -446,6 +559,7 @@ fn render_resolved_path(path: &Path) -> Vec<Token> {
 
 fn render_function_pointer(ptr: &FunctionPointer) -> Vec<Token> {
     let mut output = render_higher_rank_trait_bounds(&ptr.generic_params);
+    output.extend(render_function_header(&ptr.header));
     output.push(Token::kind("fn"));
     output.extend(render_fn_decl(&ptr.decl));
     output
@@ -522,7 +636,12 @@ fn render_borrowed_ref(lifetime: Option<&str>, mutable: bool, type_: &Type) -> V
     output
 }
 
-fn render_qualified_path(type_: &Type, trait_: &Path, name: &str) -> Vec<Token> {
+fn render_qualified_path(
+    type_: &Type,
+    trait_: &Path,
+    name: &str,
+    args: &GenericArgs,
+) -> Vec<Token> {
     let mut output = vec![];
     match type_ {
         Type::Generic(name) if name == "Self" && trait_.name.is_empty() => {
@@ -538,17 +657,20 @@ fn render_qualified_path(type_: &Type, trait_: &Path, name: &str) -> Vec<Token>
     }
     output.push(Token::symbol("::"));
     output.push(Token::identifier(name));
+    output.extend(render_generic_args(args));
     output
 }
 
 fn render_generic_args(args: &GenericArgs) -> Vec<Token> {
     match args {
         GenericArgs::AngleBracketed { args, bindings } => render_angle_bracketed(args, bindings),
-        GenericArgs::Parenthesized { inputs, output } => render_parenthesized(inputs, output),
+        GenericArgs::Parenthesized { inputs, output } => {
+            render_parenthesized(inputs, output.as_ref())
+        }
     }
 }
 
-fn render_parenthesized(inputs: &[Type], return_ty: &Option<Type>) -> Vec<Token> {
+fn render_parenthesized(inputs: &[Type], return_ty: Option<&Type>) -> Vec<Token> {
     let mut output = render_sequence(
         vec![Token::symbol("(")],
         vec![Token::symbol(")")],
@@ -674,19 +796,29 @@ fn render_generic_param_def(generic_param_def: &GenericParamDef) -> Vec<Token> {
                 }));
             }
         }
-        GenericParamDefKind::Type { bounds, .. } => {
+        GenericParamDefKind::Type {
+            bounds, default, ..
+        } => {
             output.push(Token::generic(&generic_param_def.name));
             if !bounds.is_empty() {
                 output.extend(colon());
                 output.extend(render_generic_bounds(bounds));
             }
+            if let Some(ty) = default {
+                output.extend(equals());
+                output.extend(render_type(ty));
+            }
         }
-        GenericParamDefKind::Const { type_, .. } => {
+        GenericParamDefKind::Const { type_, default } => {
             output.push(Token::qualifier("const"));
             output.push(ws!());
             output.push(Token::identifier(&generic_param_def.name));
             output.extend(colon());
             output.extend(render_type(type_));
+            if let Some(default) = default {
+                output.extend(equals());
+                output.push(Token::identifier(default));
+            }
         }
     }
     output
@@ -722,10 +854,11 @@ fn render_where_predicate(where_predicate: &WherePredicate) -> Vec<Token> {
             output.extend(colon());
             output.extend(render_generic_bounds(bounds));
         }
-        WherePredicate::RegionPredicate {
-            lifetime,
-            bounds: _,
-        } => output.push(Token::Lifetime(lifetime.clone())),
+        WherePredicate::RegionPredicate { lifetime, bounds } => {
+            output.push(Token::Lifetime(lifetime.clone()));
+            output.extend(colon());
+            output.extend(render_generic_bounds(bounds));
+        }
         WherePredicate::EqPredicate { lhs, rhs } => {
             output.extend(render_type(lhs));
             output.extend(equals());
@@ -740,10 +873,17 @@ fn render_generic_bounds(bounds: &[GenericBound]) -> Vec<Token> {
         GenericBound::TraitBound {
             trait_,
             generic_params,
-            ..
+            modifier,
         } => {
             let mut output = vec![];
             output.extend(render_higher_rank_trait_bounds(generic_params));
+            match modifier {
+                TraitBoundModifier::None => {}
+                TraitBoundModifier::Maybe => output.push(Token::symbol("?")),
+                TraitBoundModifier::MaybeConst => {
+                    output.extend(vec![Token::symbol("~"), Token::qualifier("const"), ws!()]);
+                }
+            }
             output.extend(render_resolved_path(trait_));
             output
         }
@@ -1092,6 +1232,130 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_type_path_with_args() {
+        assert_render(
+            render_type(&Type::QualifiedPath {
+                name: s!("name"),
+                args: Box::new(GenericArgs::AngleBracketed {
+                    args: vec![GenericArg::Lifetime(s!("'a"))],
+                    bindings: vec![],
+                }),
+                self_type: Box::new(Type::Generic(s!("type"))),
+                trait_: Path {
+                    name: String::from("trait"),
+                    args: None,
+                    id: Id(s!("id")),
+                },
+            }),
+            vec![
+                Token::symbol("<"),
+                Token::generic("type"),
+                ws!(),
+                Token::keyword("as"),
+                ws!(),
+                Token::type_("trait"),
+                Token::symbol(">"),
+                Token::symbol("::"),
+                Token::identifier("name"),
+                Token::symbol("<"),
+                Token::lifetime("'a"),
+                Token::symbol(">"),
+            ],
+            "<type as trait>::name<'a>",
+        );
+    }
+
+    #[test]
+    fn test_bounds_with_modifiers() {
+        let trait_bound = |name: &str, modifier| GenericBound::TraitBound {
+            trait_: Path {
+                name: name.to_owned(),
+                args: None,
+                id: Id(s!("id")),
+            },
+            generic_params: vec![],
+            modifier,
+        };
+        assert_render(
+            render_generic_bounds(&[
+                trait_bound("Sized", TraitBoundModifier::Maybe),
+                trait_bound("Trait", TraitBoundModifier::MaybeConst),
+                trait_bound("Debug", TraitBoundModifier::None),
+            ]),
+            vec![
+                Token::symbol("?"),
+                Token::type_("Sized"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::symbol("~"),
+                Token::qualifier("const"),
+                ws!(),
+                Token::type_("Trait"),
+                ws!(),
+                Token::symbol("+"),
+                ws!(),
+                Token::type_("Debug"),
+            ],
+            "?Sized + ~const Trait + Debug",
+        );
+    }
+
+    #[test]
+    fn test_abis() {
+        let abis = [
+            (Abi::Rust, None),
+            (Abi::C { unwind: false }, Some("\"C\"")),
+            (Abi::C { unwind: true }, Some("\"C-unwind\"")),
+            (Abi::Cdecl { unwind: false }, Some("\"cdecl\"")),
+            (Abi::Stdcall { unwind: true }, Some("\"stdcall-unwind\"")),
+            (Abi::Fastcall { unwind: false }, Some("\"fastcall\"")),
+            (Abi::Aapcs { unwind: false }, Some("\"aapcs\"")),
+            (Abi::Win64 { unwind: false }, Some("\"win64\"")),
+            (Abi::SysV64 { unwind: false }, Some("\"sysv64\"")),
+            (Abi::System { unwind: true }, Some("\"system-unwind\"")),
+            (Abi::Other(s!("\"vectorcall\"")), Some("\"vectorcall\"")),
+            (Abi::Other(s!("efiapi")), Some("\"efiapi\"")),
+        ];
+        for (abi, expected) in abis {
+            assert_eq!(render_abi(&abi).as_deref(), expected);
+        }
+    }
+
+    #[test]
+    fn test_type_function_pointer() {
+        assert_render(
+            render_type(&Type::FunctionPointer(Box::new(FunctionPointer {
+                decl: FnDecl {
+                    inputs: vec![(s!("_"), Type::Primitive(s!("i32")))],
+                    output: None,
+                    c_variadic: false,
+                },
+                generic_params: vec![],
+                header: Header {
+                    const_: false,
+                    unsafe_: true,
+                    async_: false,
+                    abi: Abi::SysV64 { unwind: false },
+                },
+            }))),
+            vec![
+                Token::qualifier("unsafe"),
+                ws!(),
+                Token::qualifier("extern"),
+                ws!(),
+                Token::symbol("\"sysv64\""),
+                ws!(),
+                Token::kind("fn"),
+                Token::symbol("("),
+                Token::primitive("i32"),
+                Token::symbol(")"),
+            ],
+            "unsafe extern \"sysv64\" fn(i32)",
+        );
+    }
+
     #[allow(clippy::needless_pass_by_value)]
     fn assert_render(actual: Vec<Token>, expected: Vec<Token>, expected_string: &str) {
         assert_eq!(actual, expected);
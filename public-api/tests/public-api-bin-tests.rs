@@ -183,7 +183,7 @@ fn print_minimum_rustdoc_json_version() {
     let mut cmd = Command::cargo_bin("public-api").unwrap();
     cmd.arg("--print-minimum-rustdoc-json-version");
     cmd.assert()
-        .stdout(format!("{}\n", MINIMUM_RUSTDOC_JSON_VERSION))
+        .stdout(format!("{MINIMUM_RUSTDOC_JSON_VERSION}\n"))
         .stderr("")
         .success();
 }
@@ -191,7 +191,7 @@ fn print_minimum_rustdoc_json_version() {
 #[test]
 fn too_many_args_shows_help() {
     let mut cmd = Command::cargo_bin("public-api").unwrap();
-    cmd.args(&["too", "many", "args"]);
+    cmd.args(["too", "many", "args"]);
     cmd.assert()
         .stdout(expected_help_text())
         .stderr("")
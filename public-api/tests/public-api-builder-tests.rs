@@ -0,0 +1,107 @@
+// deny in CI, only warn here
+#![warn(clippy::all, clippy::pedantic)]
+
+use pretty_assertions::assert_eq;
+use public_api::{Error, Options, PublicApi};
+use rustdoc_json::{BuildError, BuildOptions};
+
+#[test]
+fn build_from_manifest_path() {
+    let public_api = builder("../test-apis/example_api-v0.2.0").build().unwrap();
+
+    assert_eq!(
+        into_strings(public_api.items),
+        [
+            "#[non_exhaustive] pub struct example_api::Struct",
+            "pub fn example_api::Struct::fmt(&self, f: &mut $crate::fmt::Formatter<'_>) -> $crate::fmt::Result",
+            "pub fn example_api::function(v1_param: Struct, v2_param: usize)",
+            "pub mod example_api",
+            "pub struct example_api::StructV2",
+            "pub struct field example_api::Struct::v1_field: usize",
+            "pub struct field example_api::Struct::v2_field: usize",
+            "pub struct field example_api::StructV2::field: usize",
+        ]
+    );
+}
+
+#[test]
+fn build_with_features() {
+    let public_api = builder("../test-apis/features")
+        .no_default_features(true)
+        .features(["feature_b"])
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        into_strings(public_api.items),
+        [
+            "#[non_exhaustive] pub struct features::AStruct",
+            "pub mod features",
+            "pub struct field features::AStruct::feature_b: ()",
+        ]
+    );
+}
+
+#[test]
+fn build_with_options() {
+    let mut options = Options::default();
+    options.with_blanket_implementations = true;
+    let public_api = builder("../test-apis/example_api-v0.2.0")
+        .options(options)
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        into_strings(public_api.items).join("\n") + "\n",
+        include_str!("./expected-output/example_api-v0.2.0-with-blanket-implementations.txt"),
+    );
+}
+
+#[test]
+fn build_with_build_options() {
+    let public_api = PublicApi::builder()
+        .build_options(
+            BuildOptions::default()
+                .toolchain("+nightly".to_owned())
+                .manifest_path("../test-apis/features/Cargo.toml")
+                .all_features(true)
+                .quiet(true),
+        )
+        .build()
+        .unwrap();
+
+    assert_eq!(public_api.items.len(), 5);
+}
+
+#[test]
+fn build_with_rustdoc_json_path() {
+    let (public_api, json_path) = builder("../test-apis/example_api-v0.2.0")
+        .build_with_rustdoc_json_path()
+        .unwrap();
+
+    assert_eq!(public_api.items.len(), 8);
+    assert!(json_path.ends_with("doc/example_api.json"));
+}
+
+#[test]
+fn build_virtual_manifest() {
+    let result = builder("../cargo-public-api/tests/virtual-manifest").build();
+
+    assert!(matches!(
+        result,
+        Err(Error::BuildError(BuildError::VirtualManifest(_)))
+    ));
+}
+
+/// Returns a builder for the test crate at `test_crate` that builds quietly,
+/// since the test framework can not capture output from child processes.
+fn builder(test_crate: &str) -> public_api::PublicApiBuilder {
+    PublicApi::builder()
+        .toolchain("+nightly".to_owned())
+        .manifest_path(format!("{test_crate}/Cargo.toml"))
+        .quiet(true)
+}
+
+fn into_strings(items: Vec<impl std::fmt::Display>) -> Vec<String> {
+    items.into_iter().map(|x| format!("{x}")).collect()
+}
@@ -0,0 +1,81 @@
+// deny in CI, only warn here
+#![warn(clippy::all, clippy::pedantic)]
+
+use std::path::PathBuf;
+use std::process::Command;
+
+use pretty_assertions::assert_eq;
+use public_api::{public_api_from_rustdoc_json_str, Options};
+
+// rust-analyzer bug: https://github.com/rust-lang/rust-analyzer/issues/9173
+#[path = "../../test-utils/src/lib.rs"]
+mod test_utils;
+use test_utils::rustdoc_json_str_for_crate;
+
+/// Builds format version 20
+const FORMAT_VERSION_20_TOOLCHAIN: &str = "nightly-2022-09-07";
+
+/// Builds format version 22
+const FORMAT_VERSION_22_TOOLCHAIN: &str = "nightly-2022-10-15";
+
+#[test]
+fn format_version_20() {
+    assert_eq!(
+        public_api_for(&rustdoc_json_str_for_toolchain(
+            "../test-apis/example_api-v0.2.0",
+            FORMAT_VERSION_20_TOOLCHAIN,
+        )),
+        public_api_for(&rustdoc_json_str_for_crate(
+            "../test-apis/example_api-v0.2.0"
+        )),
+    );
+}
+
+#[test]
+fn format_version_22() {
+    assert_eq!(
+        public_api_for(&rustdoc_json_str_for_toolchain(
+            "../test-apis/comprehensive_api",
+            FORMAT_VERSION_22_TOOLCHAIN,
+        )),
+        include_str!("./expected-output/comprehensive_api.txt")
+            .lines()
+            .map(String::from)
+            .collect::<Vec<_>>(),
+    );
+}
+
+fn public_api_for(rustdoc_json_str: &str) -> Vec<String> {
+    public_api_from_rustdoc_json_str(rustdoc_json_str, Options::default())
+        .unwrap()
+        .items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect()
+}
+
+/// Builds rustdoc JSON with a specific toolchain. We can't use `rustdoc_json`
+/// for this, since tests can be configured to override the toolchain it uses.
+fn rustdoc_json_str_for_toolchain(test_crate: &str, toolchain: &str) -> String {
+    let target_dir = PathBuf::from(test_crate).join("target").join(toolchain);
+    let status = Command::new("cargo")
+        .arg(format!("+{toolchain}"))
+        .args(["rustdoc", "--quiet", "--lib", "--manifest-path"])
+        .arg(PathBuf::from(test_crate).join("Cargo.toml"))
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .args(["--", "-Z", "unstable-options", "--output-format", "json"])
+        .status()
+        .unwrap();
+    assert!(status.success());
+
+    let crate_name = PathBuf::from(test_crate)
+        .file_name()
+        .unwrap()
+        .to_string_lossy()
+        .split('-')
+        .next()
+        .unwrap()
+        .to_owned();
+    std::fs::read_to_string(target_dir.join("doc").join(format!("{crate_name}.json"))).unwrap()
+}
@@ -4,12 +4,17 @@
 use std::fmt::Display;
 
 use pretty_assertions::assert_eq;
-use public_api::{public_api_from_rustdoc_json_str, Error, Options};
+use public_api::{
+    leaks::leaked_types_from_rustdoc_json_str, public_api_from_rustdoc_json_path,
+    public_api_from_rustdoc_json_paths, public_api_from_rustdoc_json_reader,
+    public_api_from_rustdoc_json_str, DiagnosticKind, Error, Options, PublicApi, PublicItem,
+    MINIMUM_RUSTDOC_JSON_VERSION,
+};
 
 // rust-analyzer bug: https://github.com/rust-lang/rust-analyzer/issues/9173
 #[path = "../../test-utils/src/lib.rs"]
 mod test_utils;
-use test_utils::rustdoc_json_str_for_crate;
+use test_utils::{rustdoc_json_path_for_crate, rustdoc_json_str_for_crate};
 
 struct ExpectedDiff<'a> {
     removed: &'a [&'a str],
@@ -51,6 +56,24 @@ fn diff_with_added_items() {
     );
 }
 
+/// A struct that gains its first private field can no longer be constructed
+/// with a struct literal, so that must show up in the diff
+#[test]
+fn diff_with_struct_gaining_private_field() {
+    assert_public_api_diff(
+        &rustdoc_json_str_for_crate("../test-apis/private_fields-v0.1.0"),
+        &rustdoc_json_str_for_crate("../test-apis/private_fields-v0.2.0"),
+        &ExpectedDiff {
+            removed: &[],
+            changed: &[(
+                "pub struct private_fields::Struct",
+                "pub struct private_fields::Struct { /* private fields */ }",
+            )],
+            added: &[],
+        },
+    );
+}
+
 #[test]
 fn no_diff() {
     // No change to the public API
@@ -107,6 +130,91 @@ fn comprehensive_api_proc_macro() {
     );
 }
 
+#[test]
+fn comprehensive_api_from_path() {
+    let json_path = rustdoc_json_path_for_crate("../test-apis/comprehensive_api");
+    let public_api = public_api_from_rustdoc_json_path(json_path, Options::default()).unwrap();
+
+    assert_eq!(
+        into_strings(public_api.items),
+        expected_output_to_string_vec(include_str!("./expected-output/comprehensive_api.txt")),
+    );
+}
+
+#[test]
+#[cfg(feature = "mmap")]
+fn comprehensive_api_from_mmap() {
+    use public_api::public_api_from_rustdoc_json_mmap;
+
+    // Other tests rebuild the rustdoc JSON concurrently, so map a copy that
+    // nothing else touches
+    let json_path = std::env::temp_dir().join("public-api-comprehensive_api-mmap.json");
+    std::fs::copy(
+        rustdoc_json_path_for_crate("../test-apis/comprehensive_api"),
+        &json_path,
+    )
+    .unwrap();
+
+    // SAFETY: Nothing modifies the copy while it is mapped
+    let public_api =
+        unsafe { public_api_from_rustdoc_json_mmap(&json_path, Options::default()) }.unwrap();
+
+    assert_eq!(
+        into_strings(public_api.items),
+        expected_output_to_string_vec(include_str!("./expected-output/comprehensive_api.txt")),
+    );
+}
+
+#[test]
+fn comprehensive_api_from_reader() {
+    let json_path = rustdoc_json_path_for_crate("../test-apis/comprehensive_api");
+    let reader = std::fs::File::open(json_path).unwrap();
+    let public_api = public_api_from_rustdoc_json_reader(reader, Options::default()).unwrap();
+
+    assert_eq!(
+        into_strings(public_api.items),
+        expected_output_to_string_vec(include_str!("./expected-output/comprehensive_api.txt")),
+    );
+}
+
+#[test]
+fn public_api_from_many_paths() {
+    let comprehensive_api = rustdoc_json_path_for_crate("../test-apis/comprehensive_api");
+    let example_api = rustdoc_json_path_for_crate("../test-apis/example_api-v0.2.0");
+    let paths = [
+        comprehensive_api.clone(),
+        "does/not/exist.json".into(),
+        example_api.clone(),
+    ];
+
+    let mut results = public_api_from_rustdoc_json_paths(&paths, Options::default()).into_iter();
+
+    for expected_path in [Some(comprehensive_api), None, Some(example_api)] {
+        let result = results.next().unwrap();
+        if let Some(expected_path) = expected_path {
+            let expected = public_api_from_rustdoc_json_path(expected_path, Options::default());
+            assert_eq!(result.unwrap().items, expected.unwrap().items);
+        } else {
+            assert!(matches!(result, Err(Error::IoError(_))));
+        }
+    }
+    assert!(results.next().is_none());
+}
+
+#[test]
+fn public_api_is_send_and_sync() {
+    fn assert_send_and_sync<T: Send + Sync>() {}
+    assert_send_and_sync::<PublicApi>();
+    assert_send_and_sync::<PublicItem>();
+    assert_send_and_sync::<Error>();
+}
+
+#[test]
+fn missing_rustdoc_json_path() {
+    let result = public_api_from_rustdoc_json_path("does/not/exist.json", Options::default());
+    assert!(matches!(result, Err(Error::IoError(_))));
+}
+
 /// I confess: this test is mainly to get function code coverage on Ord
 #[test]
 fn public_item_ord() {
@@ -120,18 +228,214 @@ fn public_item_ord() {
         .items
         .clone()
         .into_iter()
-        .find(|x| format!("{}", x).contains("generic_arg"))
+        .find(|x| format!("{x}").contains("generic_arg"))
         .unwrap();
 
     let generic_bound = public_api
         .items
         .into_iter()
-        .find(|x| format!("{}", x).contains("generic_bound"))
+        .find(|x| format!("{x}").contains("generic_bound"))
         .unwrap();
 
     assert_eq!(generic_arg.max(generic_bound.clone()), generic_bound);
 }
 
+#[test]
+fn reexport_provenance() {
+    let public_api = public_api_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        Options::default(),
+    )
+    .unwrap();
+
+    let renamed_plain = public_api
+        .items
+        .iter()
+        .find(|x| format!("{x}") == "pub struct comprehensive_api::RenamedPlain")
+        .unwrap();
+    let reexport = renamed_plain.reexport().unwrap();
+    ensure_impl_debug(reexport);
+    assert_eq!(
+        reexport.original_path(),
+        ["comprehensive_api", "structs", "Plain"]
+    );
+    assert_eq!(reexport.crate_name(), "comprehensive_api");
+    assert!(!reexport.is_external());
+
+    let plain = public_api
+        .items
+        .iter()
+        .find(|x| format!("{x}") == "pub struct comprehensive_api::structs::Plain")
+        .unwrap();
+    assert!(plain.reexport().is_none());
+}
+
+#[test]
+fn span() {
+    let public_api = public_api_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        Options::default(),
+    )
+    .unwrap();
+
+    let plain = public_api
+        .items
+        .iter()
+        .find(|x| format!("{x}") == "pub struct comprehensive_api::structs::Plain")
+        .unwrap();
+    let span = plain.span().unwrap();
+    assert_eq!(span.filename(), std::path::Path::new("src/structs.rs"));
+    assert!(span.begin().0 <= span.end().0);
+    assert_eq!(
+        format!("{span}"),
+        format!("src/structs.rs:{}", span.begin().0)
+    );
+}
+
+#[test]
+fn with_reexports() {
+    let mut options = Options::default();
+    options.with_reexports = true;
+    let public_api = public_api_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        options,
+    )
+    .unwrap();
+
+    let items = into_strings(public_api.items);
+    assert!(items.contains(&String::from(
+        "pub use comprehensive_api::structs::Plain as comprehensive_api::RenamedPlain"
+    )));
+    assert!(items.contains(&String::from("pub struct comprehensive_api::RenamedPlain")));
+}
+
+/// Items re-exported from other crates are typically missing from the rustdoc
+/// JSON, but we still know where they come from
+#[test]
+fn with_reexports_of_foreign_item() {
+    let mut options = Options::default();
+    options.with_reexports = true;
+    let public_api = public_api_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        options,
+    )
+    .unwrap();
+
+    let items = into_strings(public_api.items);
+    assert!(items.contains(&String::from(
+        "pub use example_api::StructV2 as comprehensive_api::StructV2"
+    )));
+    assert!(items.contains(&String::from("pub use comprehensive_api::StructV2")));
+}
+
+#[test]
+fn leaked_types() {
+    let leaked_types = leaked_types_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        Options::default(),
+    )
+    .unwrap();
+    ensure_impl_debug(&leaked_types);
+
+    assert_eq!(leaked_types.crates.len(), 1);
+    let example_api = &leaked_types.crates[0];
+    assert_eq!(example_api.name, "example_api");
+
+    let struct_ = example_api
+        .types
+        .iter()
+        .find(|t| t.path == "example_api::Struct")
+        .unwrap();
+    assert_eq!(
+        struct_.used_by,
+        ["comprehensive_api::functions::dependency_types"]
+    );
+
+    // `pub extern crate example_api` re-exports a crate, not a type
+    assert!(!example_api.types.iter().any(|t| t.path == "example_api"));
+
+    assert!(leaked_types.not_allowed(&["example_api"]).is_empty());
+    assert_eq!(leaked_types.not_allowed(&["std"]).len(), 1);
+}
+
+#[test]
+fn warnings() {
+    let public_api = public_api_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        Options::default(),
+    )
+    .unwrap();
+
+    let warnings = public_api
+        .warnings
+        .iter()
+        .map(|w| (w.kind.clone(), w.path.as_str()))
+        .collect::<Vec<_>>();
+    assert!(warnings.contains(&(
+        DiagnosticKind::GlobImportCycle {
+            source: String::from("super::recursion_glob_1")
+        },
+        "comprehensive_api::exports::recursion_glob_1"
+    )));
+    assert!(warnings.contains(&(DiagnosticKind::MissingItem, "example_api::Struct")));
+
+    // Placeholders for items that could not be listed must not end up among
+    // the items
+    assert!(!public_api
+        .items
+        .iter()
+        .any(|item| item.to_string().contains("<<")));
+
+    // Glob imports that can't be inlined are still part of the public API
+    let items = into_strings(public_api.items);
+    assert!(items.contains(&String::from("pub use comprehensive_api::example_api::*")));
+}
+
+#[test]
+#[allow(deprecated)]
+fn missing_item_ids() {
+    let public_api = public_api_from_rustdoc_json_str(
+        &rustdoc_json_str_for_crate("../test-apis/comprehensive_api"),
+        Options::default(),
+    )
+    .unwrap();
+
+    let missing_items = public_api
+        .warnings
+        .iter()
+        .filter(|w| w.kind == DiagnosticKind::MissingItem)
+        .map(|w| w.id.clone())
+        .collect::<Vec<_>>();
+    assert!(!missing_items.is_empty());
+    assert_eq!(public_api.missing_item_ids, missing_items);
+}
+
+/// If the module of a glob import is missing from the rustdoc JSON, its items
+/// are looked up via the paths in the rustdoc JSON instead
+#[test]
+fn glob_import_of_missing_module() {
+    let json = rustdoc_json_str_for_crate("../test-apis/comprehensive_api");
+    let mut json: serde_json::Value = serde_json::from_str(&json).unwrap();
+    let index = json["index"].as_object_mut().unwrap();
+    let v0_id = index
+        .iter()
+        .find(|(_, item)| item["name"] == "v0")
+        .map(|(id, _)| id.clone())
+        .unwrap();
+    index.remove(&v0_id);
+
+    let public_api =
+        public_api_from_rustdoc_json_str(&json.to_string(), Options::default()).unwrap();
+
+    let items = into_strings(public_api.items);
+    assert!(items.contains(&String::from(
+        "pub fn comprehensive_api::exports::v1::foo()"
+    )));
+    assert!(!items.contains(&String::from(
+        "pub use comprehensive_api::exports::v1::super::v0::*"
+    )));
+}
+
 #[test]
 fn invalid_json() {
     let result = public_api_from_rustdoc_json_str("}}}}}}}}}", Options::default());
@@ -139,6 +443,57 @@ fn invalid_json() {
     assert!(matches!(result, Err(Error::SerdeJsonError(_))));
 }
 
+#[test]
+fn too_old_format_version() {
+    let result = public_api_from_rustdoc_json_str(r#"{"format_version":1}"#, Options::default());
+    let error = result.unwrap_err();
+    assert!(matches!(
+        error,
+        Error::UnsupportedFormatVersion { found: 1, .. }
+    ));
+    assert!(error.to_string().contains("is too old"));
+    assert!(error.to_string().contains(MINIMUM_RUSTDOC_JSON_VERSION));
+}
+
+#[test]
+fn too_new_format_version() {
+    let result = public_api_from_rustdoc_json_str(
+        r#"{"root":"0:0","format_version":9999}"#,
+        Options::default(),
+    );
+    let error = result.unwrap_err();
+    assert!(matches!(
+        error,
+        Error::UnsupportedFormatVersion { found: 9999, .. }
+    ));
+    assert!(error.to_string().contains(MINIMUM_RUSTDOC_JSON_VERSION));
+}
+
+#[test]
+fn too_new_format_version_from_reader() {
+    let json = rustdoc_json_str_for_crate("../test-apis/example_api-v0.2.0");
+    let json = json.replace(
+        &format!(r#""format_version":{}"#, rustdoc_types::FORMAT_VERSION),
+        r#""format_version":9999"#,
+    );
+
+    let result = public_api_from_rustdoc_json_reader(json.as_bytes(), Options::default());
+    assert!(matches!(
+        result,
+        Err(Error::UnsupportedFormatVersion { found: 9999, .. })
+    ));
+}
+
+#[test]
+#[cfg(not(feature = "format-version-20"))]
+fn format_version_behind_cargo_feature() {
+    let result = public_api_from_rustdoc_json_str(r#"{"format_version":20}"#, Options::default());
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("`format-version-20` cargo feature"));
+}
+
 #[test]
 fn options() {
     let options = Options::default();
@@ -165,7 +520,7 @@ fn pretty_printed_diff() {
     .unwrap();
 
     let diff = public_api::diff::PublicItemsDiff::between(old.items, new.items);
-    let pretty_printed = format!("{:#?}", diff);
+    let pretty_printed = format!("{diff:#?}");
     assert_eq!(
         pretty_printed,
         "PublicItemsDiff {
@@ -248,11 +603,11 @@ fn expected_output_to_string_vec(expected_output: &str) -> Vec<String> {
 }
 
 fn into_strings(items: Vec<impl Display>) -> Vec<String> {
-    items.into_iter().map(|x| format!("{}", x)).collect()
+    items.into_iter().map(|x| format!("{x}")).collect()
 }
 
 /// To be honest this is mostly to get higher code coverage numbers.
 /// But it is actually useful thing to test.
 fn ensure_impl_debug(impl_debug: &impl std::fmt::Debug) {
-    eprintln!("Yes, this can be debugged: {:?}", impl_debug);
+    eprintln!("Yes, this can be debugged: {impl_debug:?}");
 }
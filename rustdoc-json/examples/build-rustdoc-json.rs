@@ -9,7 +9,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let json_path = rustdoc_json::build(
         BuildOptions::default()
             .toolchain("+nightly".to_owned())
-            .manifest_path(&std::env::args().nth(1).unwrap()),
+            .manifest_path(std::env::args().nth(1).unwrap()),
     )?;
     println!("Built and wrote rustdoc JSON to {:?}", &json_path);
 
@@ -23,6 +23,6 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
 fn show_json(path: &std::path::Path) -> std::io::Result<std::process::ExitStatus> {
     let mut cmd = std::process::Command::new("sh");
     cmd.arg("-c");
-    cmd.arg(&format!("cat {:?} | python3 -m json.tool | less", path));
+    cmd.arg(format!("cat {:?} | python3 -m json.tool | less", path));
     cmd.spawn()?.wait()
 }
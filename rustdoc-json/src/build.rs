@@ -1,6 +1,7 @@
 use super::BuildError;
 use super::BuildOptions;
 
+use cargo_metadata::{Metadata, Package, Target};
 use std::{
     path::{Path, PathBuf},
     process::Command,
@@ -13,20 +14,49 @@ const OVERRIDDEN_TOOLCHAIN: Option<&str> = option_env!("RUSTDOC_JSON_OVERRIDDEN_
 
 /// Run `cargo rustdoc` to produce rustdoc JSON and return the path to the built
 /// file.
-pub(crate) fn run_cargo_rustdoc(options: BuildOptions) -> Result<PathBuf, BuildError> {
+pub(crate) fn run_cargo_rustdoc(mut options: BuildOptions) -> Result<PathBuf, BuildError> {
+    // We only need the metadata up front to see if `--target` applies. Keep it
+    // around so that we do not run `cargo metadata` again afterwards.
+    let metadata = if options.target.is_some() {
+        metadata_without_deps(&options.manifest_path).ok()
+    } else {
+        None
+    };
+    if let Some(metadata) = &metadata {
+        if is_proc_macro_crate(metadata, &options.manifest_path, options.package.as_deref()) {
+            options.target = None;
+        }
+    }
+
     let mut cmd = cargo_rustdoc_command(&options);
     if cmd.status()?.success() {
         rustdoc_json_path_for_manifest_path(
+            metadata,
             options.manifest_path,
             options.package.as_deref(),
             options.target.as_deref(),
         )
     } else {
         let manifest = cargo_toml::Manifest::from_path(&options.manifest_path)?;
-        if manifest.package.is_none() && manifest.workspace.is_some() {
+        if manifest.package.is_none() && manifest.workspace.is_some() && options.package.is_none() {
             Err(BuildError::VirtualManifest(options.manifest_path))
         } else {
-            Err(BuildError::General(String::from("See above")))
+            // `cargo rustdoc --lib` fails if there is no lib target. Give a
+            // clearer error in that case.
+            let metadata =
+                metadata.map_or_else(|| metadata_without_deps(&options.manifest_path), Ok);
+            let lib_target = metadata.and_then(|metadata| {
+                lib_target(
+                    &metadata,
+                    &options.manifest_path,
+                    options.package.as_deref(),
+                )
+                .map(|_| ())
+            });
+            match lib_target {
+                Err(e @ BuildError::NoLibTarget(_)) => Err(e),
+                _ => Err(BuildError::General(String::from("See above"))),
+            }
         }
     }
 }
@@ -88,21 +118,25 @@ fn cargo_rustdoc_command(options: &BuildOptions) -> Command {
 }
 
 /// Returns `./target/doc/crate_name.json`. Also takes care of transforming
-/// `crate-name` to `crate_name`.
+/// `crate-name` to `crate_name`. Uses `metadata` if we already have it.
 fn rustdoc_json_path_for_manifest_path(
+    metadata: Option<Metadata>,
     manifest_path: impl AsRef<Path>,
     package: Option<&str>,
     target: Option<&str>,
 ) -> Result<PathBuf, BuildError> {
-    let target_dir = target_directory(&manifest_path)?;
-    let lib_name = package
-        .map(ToOwned::to_owned)
-        .map_or_else(|| package_name(&manifest_path), Ok)?;
+    let metadata = match metadata {
+        Some(metadata) => metadata,
+        None => self::metadata(&manifest_path)?,
+    };
+    let lib_name = &lib_target(&metadata, manifest_path.as_ref(), package)?.name;
 
-    let mut rustdoc_json_path = target_dir;
+    // Typically the absolute path to the regular cargo `./target` directory.
+    // But also handles packages part of workspaces.
+    let mut rustdoc_json_path = metadata.target_directory.as_std_path().to_owned();
     // if one has specified a target explicitly then Cargo appends that target triple name as a subfolder
     if let Some(target) = target {
-        rustdoc_json_path.push(&target);
+        rustdoc_json_path.push(target);
     }
     rustdoc_json_path.push("doc");
     rustdoc_json_path.push(lib_name.replace('-', "_"));
@@ -110,23 +144,115 @@ fn rustdoc_json_path_for_manifest_path(
     Ok(rustdoc_json_path)
 }
 
-/// Typically returns the absolute path to the regular cargo `./target`
-/// directory. But also handles packages part of workspaces.
-fn target_directory(manifest_path: impl AsRef<Path>) -> Result<PathBuf, BuildError> {
+fn metadata(manifest_path: impl AsRef<Path>) -> Result<Metadata, BuildError> {
+    let mut metadata_cmd = cargo_metadata::MetadataCommand::new();
+    metadata_cmd.manifest_path(manifest_path.as_ref());
+    Ok(metadata_cmd.exec()?)
+}
+
+/// Like [`metadata()`] but does not resolve dependencies. This is cheaper and
+/// does not create or update `Cargo.lock`, which is important if we have not
+/// built anything yet.
+fn metadata_without_deps(manifest_path: impl AsRef<Path>) -> Result<Metadata, BuildError> {
     let mut metadata_cmd = cargo_metadata::MetadataCommand::new();
     metadata_cmd.manifest_path(manifest_path.as_ref());
-    let metadata = metadata_cmd.exec()?;
-    Ok(metadata.target_directory.as_std_path().to_owned())
+    metadata_cmd.no_deps();
+    Ok(metadata_cmd.exec()?)
 }
 
-/// Figures out the name of the library crate corresponding to the given
-/// `Cargo.toml` manifest path.
-fn package_name(manifest_path: impl AsRef<Path>) -> Result<String, BuildError> {
-    let manifest = cargo_toml::Manifest::from_path(&manifest_path)?;
-    Ok(manifest
-        .package
-        .ok_or_else(|| BuildError::VirtualManifest(manifest_path.as_ref().to_owned()))?
-        .name)
+/// Figures out the library target of the package corresponding to the given
+/// `Cargo.toml` manifest path, or of `package` if specified. Note that the name
+/// of the target is not necessarily the same as the package name, since it can
+/// be changed with `[lib] name = "..."`.
+fn lib_target<'a>(
+    metadata: &'a Metadata,
+    manifest_path: &Path,
+    package: Option<&str>,
+) -> Result<&'a Target, BuildError> {
+    let package = select_package(metadata, manifest_path, package)?;
+    package
+        .targets
+        .iter()
+        .find(|target| is_lib_target(target))
+        .ok_or_else(|| BuildError::NoLibTarget(package.name.clone()))
+}
+
+/// See [`crate::features()`].
+pub(crate) fn features(
+    manifest_path: &Path,
+    package: Option<&str>,
+) -> Result<Vec<String>, BuildError> {
+    let metadata = metadata_without_deps(manifest_path)?;
+    let package = select_package(&metadata, manifest_path, package)?;
+    let mut features = package.features.keys().cloned().collect::<Vec<_>>();
+    features.sort();
+    Ok(features)
+}
+
+/// Finds the package named `package` if specified, or else the package of the
+/// given manifest.
+fn select_package<'a>(
+    metadata: &'a Metadata,
+    manifest_path: &Path,
+    package: Option<&str>,
+) -> Result<&'a Package, BuildError> {
+    if let Some(package) = package {
+        find_package(metadata, package)
+            .ok_or_else(|| BuildError::General(format!("Package `{package}` not found")))
+    } else {
+        root_package(metadata, manifest_path)
+            .ok_or_else(|| BuildError::VirtualManifest(manifest_path.to_owned()))
+    }
+}
+
+/// Finds the package of the given manifest. Unlike
+/// [`Metadata::root_package()`] this also works if dependencies were not
+/// resolved.
+fn root_package<'a>(metadata: &'a Metadata, manifest_path: &Path) -> Option<&'a Package> {
+    metadata.root_package().or_else(|| {
+        let manifest_path = manifest_path.canonicalize().ok()?;
+        metadata
+            .packages
+            .iter()
+            .find(|p| p.manifest_path.as_std_path() == manifest_path)
+    })
+}
+
+/// Finds the package with the given name. Workspace members take precedence
+/// over dependencies with the same name.
+fn find_package<'a>(metadata: &'a Metadata, name: &str) -> Option<&'a Package> {
+    let mut candidates = metadata.packages.iter().filter(|p| p.name == name);
+    candidates
+        .clone()
+        .find(|p| metadata.workspace_members.contains(&p.id))
+        .or_else(|| candidates.next())
+}
+
+/// See [`crate::is_proc_macro()`].
+pub(crate) fn is_proc_macro(
+    manifest_path: &Path,
+    package: Option<&str>,
+) -> Result<bool, BuildError> {
+    let metadata = metadata_without_deps(manifest_path)?;
+    Ok(is_proc_macro_crate(&metadata, manifest_path, package))
+}
+
+/// Proc-macro crates are always built for the host, so `--target` does not
+/// apply to them.
+fn is_proc_macro_crate(metadata: &Metadata, manifest_path: &Path, package: Option<&str>) -> bool {
+    lib_target(metadata, manifest_path, package).map_or(false, |target| {
+        target.kind.iter().any(|kind| kind == "proc-macro")
+    })
+}
+
+/// The target kinds that `cargo rustdoc --lib` considers to be a lib target.
+fn is_lib_target(target: &Target) -> bool {
+    target.kind.iter().any(|kind| {
+        matches!(
+            kind.as_str(),
+            "lib" | "rlib" | "dylib" | "cdylib" | "staticlib" | "proc-macro"
+        )
+    })
 }
 
 impl Default for BuildOptions {
@@ -166,7 +292,7 @@ impl BuildOptions {
     /// Set the relative or absolute path to `Cargo.toml`. Default: `Cargo.toml`
     #[must_use]
     pub fn manifest_path(mut self, manifest_path: impl AsRef<Path>) -> Self {
-        self.manifest_path = manifest_path.as_ref().to_owned();
+        manifest_path.as_ref().clone_into(&mut self.manifest_path);
         self
     }
 
@@ -178,6 +304,9 @@ impl BuildOptions {
     }
 
     /// Whether or not to pass `--target` to `cargo rustdoc`. Default: `None`
+    ///
+    /// Ignored for proc-macro crates, since they are always built for the
+    /// host. Use [`crate::is_proc_macro()`] to find out if that is the case.
     #[must_use]
     pub fn target(mut self, target: String) -> Self {
         self.target = Some(target);
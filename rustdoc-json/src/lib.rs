@@ -21,7 +21,7 @@
 // deny in CI, only warn here
 #![warn(clippy::all, clippy::pedantic, missing_docs)]
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 mod build;
 
@@ -33,6 +33,11 @@ pub enum BuildError {
     #[error("Manifest must be for an actual package. `{0:?}` is a virtual manifest")]
     VirtualManifest(PathBuf),
 
+    /// The package does not have a lib target, e.g. because it is a binary-only
+    /// crate. Only library crates have rustdoc JSON for their public API.
+    #[error("Package `{0}` has no lib target. Only library crates have a public API")]
+    NoLibTarget(String),
+
     /// A general error. Refer to the attached error message for more info.
     #[error("Failed to build rustdoc JSON. Stderr: {0}")]
     General(String),
@@ -77,3 +82,36 @@ pub struct BuildOptions {
 pub fn build(options: BuildOptions) -> Result<PathBuf, BuildError> {
     build::run_cargo_rustdoc(options)
 }
+
+/// Returns the sorted names of the features of the package that [`build()`]
+/// builds rustdoc JSON for, given the same manifest path and, optionally,
+/// package name as in [`BuildOptions`]. Includes `default` if the package has
+/// it. Dependencies are not resolved, so this does not create or update
+/// `Cargo.lock`.
+///
+/// # Errors
+///
+/// E.g. if no package is given and the manifest is a virtual manifest, or if
+/// there is no package with the given name.
+pub fn features(
+    manifest_path: impl AsRef<Path>,
+    package: Option<&str>,
+) -> Result<Vec<String>, BuildError> {
+    build::features(manifest_path.as_ref(), package)
+}
+
+/// Returns `true` if the lib target of the package is a proc-macro. Takes the
+/// same manifest path and, optionally, package name as in [`BuildOptions`].
+/// [`build()`] ignores [`BuildOptions::target`] for such packages, since they
+/// are always built for the host.
+///
+/// # Errors
+///
+/// E.g. if no package is given and the manifest is a virtual manifest, or if
+/// there is no package with the given name.
+pub fn is_proc_macro(
+    manifest_path: impl AsRef<Path>,
+    package: Option<&str>,
+) -> Result<bool, BuildError> {
+    build::is_proc_macro(manifest_path.as_ref(), package)
+}
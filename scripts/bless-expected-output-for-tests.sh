@@ -8,6 +8,10 @@ test_git_dir="/tmp/cargo-public-api-test-repo"
 rm -rf "${test_git_dir}"
 cargo run -p test-utils -- "${test_git_dir}" ./test-apis
 
+feature_matrix_git_dir="/tmp/cargo-public-api-feature-matrix-test-repo"
+rm -rf "${feature_matrix_git_dir}"
+cargo run -p test-utils -- "${feature_matrix_git_dir}" ./test-apis feature_matrix v0.1.0 v0.2.0
+
 build_for="
     comprehensive_api
     comprehensive_api_proc_macro
@@ -72,6 +76,66 @@ RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-ap
       --diff-git-checkouts "v0.1.0" "v0.2.0" > \
       "cargo-public-api/tests/expected-output/example_api_diff_v0.1.0_to_v0.2.0.txt"
 
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --show-locations > \
+      "cargo-public-api/tests/expected-output/example_api_v0.3.0_locations.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --show-locations --diff-git-checkouts "v0.1.0" "v0.2.0" > \
+      "cargo-public-api/tests/expected-output/example_api_diff_v0.1.0_to_v0.2.0_locations.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --output-format github --deny=changed --diff-git-checkouts "v0.1.0" "v0.2.0" > \
+      "cargo-public-api/tests/expected-output/example_api_diff_v0.1.0_to_v0.2.0_github.txt" || true
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --output-format junit --deny=changed --diff-git-checkouts "v0.1.0" "v0.2.0" > \
+      "cargo-public-api/tests/expected-output/example_api_diff_v0.1.0_to_v0.2.0_junit.xml" || true
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --output-format html > \
+      "cargo-public-api/tests/expected-output/example_api_v0.3.0.html"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --output-format html --diff-git-checkouts "v0.1.0" "v0.2.0" > \
+      "cargo-public-api/tests/expected-output/example_api_diff_v0.1.0_to_v0.2.0.html"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --tree > \
+      "cargo-public-api/tests/expected-output/example_api_v0.3.0_tree.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${test_git_dir}/Cargo.toml" \
+      --tree --diff-git-checkouts "v0.1.0" "v0.2.0" > \
+      "cargo-public-api/tests/expected-output/example_api_diff_v0.1.0_to_v0.2.0_tree.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "./test-apis/features/Cargo.toml" \
+      --feature-matrix > \
+      "cargo-public-api/tests/expected-output/features-matrix.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "${feature_matrix_git_dir}/Cargo.toml" \
+      --color=never --feature-matrix --diff-git-checkouts "v0.1.0" "v0.2.0" > \
+      "cargo-public-api/tests/expected-output/feature_matrix_diff_v0.1.0_to_v0.2.0.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "./test-apis/target_matrix-v0.2.0/Cargo.toml" \
+      --target x86_64-unknown-linux-gnu --target wasm32-unknown-unknown > \
+      "cargo-public-api/tests/expected-output/target_matrix.txt"
+
+RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
+      --manifest-path "./test-apis/comprehensive_api/Cargo.toml" \
+      --leaked-types > \
+      "cargo-public-api/tests/expected-output/comprehensive_api_leaked_types.txt"
+
 echo 'FIXME: Do not hardcode to `--cap-lints warn` in `rustdoc-json` crate'
 RUSTDOC_JSON_OVERRIDDEN_TOOLCHAIN_HACK=${toolchain} cargo run -p cargo-public-api -- \
       --manifest-path "./test-apis/lint_error/Cargo.toml" > \
@@ -32,3 +32,17 @@ pub enum EnumWithStrippedTupleVariants {
     DoubleFirstHidden(#[doc(hidden)] bool, bool),
     DoubleSecondHidden(bool, #[doc(hidden)] bool),
 }
+
+pub enum EnumWithHiddenVariant {
+    Visible,
+    #[doc(hidden)]
+    Hidden,
+}
+
+pub enum EnumWithStrippedStructVariant {
+    Struct {
+        x: usize,
+        #[doc(hidden)]
+        hidden: usize,
+    },
+}
@@ -57,6 +57,20 @@ pub fn generic_bound<T: Sized>(t: T) -> T {
     t
 }
 
+pub fn maybe_sized_bound<T: ?Sized + Debug>(t: &T) {}
+
+pub fn maybe_sized_where<T>(t: &T)
+where
+    T: ?Sized + Debug,
+{
+}
+
+pub fn region_bounds<'a, 'b, 'c>(x: &'a u8, y: &'b u8, z: &'c u8)
+where
+    'a: 'b + 'c,
+{
+}
+
 pub fn inferred_lifetime(foo: &'_ usize) -> usize {
     *foo
 }
@@ -109,6 +123,26 @@ pub unsafe fn unsafe_fn() {}
 
 pub async fn async_fn() {}
 
+pub const unsafe fn const_unsafe_fn() {}
+
+pub extern "C" fn extern_c() {}
+
+pub unsafe extern "C" fn unsafe_extern_c(x: i32) {}
+
+pub extern "C-unwind" fn extern_c_unwind() {}
+
+pub extern "system" fn extern_system() {}
+
+pub fn fn_pointer_arg(callback: fn(i32) -> i32) {}
+
+pub fn unsafe_extern_c_fn_pointer_arg(callback: unsafe extern "C" fn(i32) -> i32) {}
+
+pub fn extern_system_fn_pointer_arg(callback: extern "system" fn()) {}
+
 pub async fn async_fn_ret_bool() -> bool {
     true
 }
+
+pub fn dependency_types(arg: Option<example_api::Struct>) -> Vec<example_api::StructV2> {
+    vec![]
+}
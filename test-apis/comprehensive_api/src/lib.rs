@@ -1,5 +1,10 @@
 // Allow stuff that prevents us from testing unidiomatic but valid public APIs
 #![allow(unused_variables, dead_code)]
+// Stable since Rust 1.65
+#![feature(generic_associated_types)]
+// Stable since Rust 1.71
+#![feature(c_unwind)]
+#![feature(type_alias_impl_trait)]
 
 mod private;
 pub use private::StructInPrivateMod;
@@ -24,6 +24,23 @@ pub struct ConstArg<T, const N: usize> {
     pub items: [T; N],
 }
 
+pub struct WithDefaultGeneric<T = u8> {
+    pub t: T,
+}
+
+pub struct ConstArgWithDefault<const N: usize = 4> {
+    pub items: [u8; N],
+}
+
+pub struct MaybeSized<T: ?Sized> {
+    pub t: Box<T>,
+}
+
 pub struct WithTraitBounds<T: Display + Debug> {
     t: T,
 }
+
+pub struct MixedFields {
+    pub public: usize,
+    private: usize,
+}
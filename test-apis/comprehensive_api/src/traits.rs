@@ -1,3 +1,5 @@
+use std::fmt::Debug;
+
 pub trait Empty {}
 
 pub trait Simple {
@@ -31,6 +33,29 @@ pub trait TraitWithGenerics<T, U> {
     fn bar() -> <Self as TraitWithGenerics<T, U>>::Foo;
 }
 
+pub trait TraitWithDefaultGeneric<Rhs: ?Sized = Self> {
+    fn with_rhs(&self, rhs: &Rhs);
+}
+
+pub trait GenericAssociatedType {
+    type WithLifetime<'a>
+    where
+        Self: 'a;
+
+    fn qualified_path_with_args<'a>(&'a self) -> <Self as GenericAssociatedType>::WithLifetime<'a>;
+}
+
+pub trait AssociatedTypeWithBounds {
+    type Bounded: Debug + Clone;
+}
+
+pub trait GenericAssociatedTypeWithBounds {
+    type Gat<'a, T>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a + Debug;
+}
+
 // error[E0658]: associated type defaults are unstable
 // skip for now
 // pub trait AssociatedTypeDefault {
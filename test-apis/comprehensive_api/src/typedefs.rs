@@ -1,5 +1,22 @@
+use std::fmt::Debug;
+
 use crate::structs::Plain;
 
 pub type TypedefPlain = Plain;
 
 pub type RedefinedResult<T, E> = Result<T, E>;
+
+pub type OpaqueIterator = impl Iterator<Item = u8>;
+
+pub fn opaque_iterator() -> OpaqueIterator {
+    std::iter::empty()
+}
+
+pub type GenericOpaque<T>
+where
+    T: Debug,
+= impl Debug + Clone;
+
+pub fn generic_opaque<T: Debug>(t: T) -> GenericOpaque<T> {
+    0_u8
+}
@@ -1,4 +1,26 @@
+use std::fmt::Debug;
+
 pub union Basic {
     pub x: usize,
     pub y: usize,
 }
+
+#[repr(C)]
+pub union ReprC {
+    pub int: u32,
+    pub float: f32,
+}
+
+pub union WithGenerics<'a, T: Copy, U>
+where
+    U: Copy + Debug,
+{
+    pub t: T,
+    pub u: U,
+    pub r: &'a u8,
+}
+
+pub union PrivateField {
+    pub x: usize,
+    y: usize,
+}
@@ -0,0 +1,12 @@
+pub fn always_available() {}
+
+pub fn becomes_feature_gated() {}
+
+#[cfg(feature = "std")]
+pub fn becomes_non_default() {}
+
+#[cfg(feature = "std")]
+pub fn stays_default() {}
+
+#[cfg(feature = "serde")]
+pub fn serde_only() {}
@@ -0,0 +1,16 @@
+pub fn always_available() {}
+
+#[cfg(feature = "std")]
+pub fn becomes_feature_gated() {}
+
+#[cfg(feature = "serde")]
+pub fn becomes_non_default() {}
+
+#[cfg(feature = "std")]
+pub fn stays_default() {}
+
+#[cfg(feature = "serde")]
+pub fn serde_only() {}
+
+#[cfg(all(feature = "std", feature = "serde"))]
+pub fn requires_std_and_serde() {}
@@ -0,0 +1,3 @@
+pub struct Struct {
+    pub field: usize,
+}
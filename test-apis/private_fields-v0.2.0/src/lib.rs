@@ -0,0 +1,4 @@
+pub struct Struct {
+    pub field: usize,
+    private_field: usize,
+}
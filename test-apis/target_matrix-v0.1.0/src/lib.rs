@@ -0,0 +1,11 @@
+pub fn everywhere() {}
+
+#[cfg(target_os = "linux")]
+pub fn linux_only() {}
+
+#[cfg(target_family = "wasm")]
+pub fn wasm_only() {}
+
+pub fn removed_on_wasm() {}
+
+pub fn changed_on_wasm(_: u32) {}
@@ -0,0 +1,18 @@
+pub fn everywhere() {}
+
+#[cfg(target_os = "linux")]
+pub fn linux_only() {}
+
+#[cfg(target_family = "wasm")]
+pub fn wasm_only() {}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn removed_on_wasm() {}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn changed_on_wasm(_: u32) {}
+
+#[cfg(target_family = "wasm")]
+pub fn changed_on_wasm(_: u64) {}
+
+pub fn added_everywhere() {}
@@ -1,6 +1,18 @@
-use test_utils::create_test_git_repo;
+use test_utils::{create_test_git_repo, create_test_git_repo_for_versions_of};
 
+/// Usage: `create-test-git-repo DEST_DIR TEST_APIS_DIR [NAME VERSION...]`
+///
+/// Without `NAME`, the versions of `example_api` are used.
 fn main() {
-    let mut args = std::env::args_os();
-    create_test_git_repo(args.nth(1).unwrap(), args.next().unwrap());
+    let mut args = std::env::args().skip(1);
+    let dest_dir = args.next().unwrap();
+    let test_apis_dir = args.next().unwrap();
+    match args.next() {
+        Some(name) => {
+            let versions: Vec<String> = args.collect();
+            let versions: Vec<&str> = versions.iter().map(String::as_str).collect();
+            create_test_git_repo_for_versions_of(dest_dir, test_apis_dir, &name, &versions);
+        }
+        None => create_test_git_repo(dest_dir, test_apis_dir),
+    }
 }
@@ -18,6 +18,23 @@ use std::{
 /// `test_api_dir` - The `test-apis` source dir. Used to find the path to the
 /// `example_api`s.
 pub fn create_test_git_repo(dest_dir: impl AsRef<Path>, test_apis_dir: impl AsRef<Path>) {
+    create_test_git_repo_for_versions_of(
+        dest_dir,
+        test_apis_dir,
+        "example_api",
+        &["v0.1.0", "v0.1.1", "v0.2.0", "v0.3.0"],
+    );
+}
+
+/// Like [`create_test_git_repo`], but for arbitrary versions of an arbitrary
+/// test API. For each version, `{test_apis_dir}/{name}-{version}` is turned into
+/// a commit and a tag named `version`.
+pub fn create_test_git_repo_for_versions_of(
+    dest_dir: impl AsRef<Path>,
+    test_apis_dir: impl AsRef<Path>,
+    name: &str,
+    versions: &[&str],
+) {
     // Make sure the dest exists
     fs::create_dir_all(&dest_dir).unwrap();
 
@@ -37,14 +54,14 @@ pub fn create_test_git_repo(dest_dir: impl AsRef<Path>, test_apis_dir: impl AsRe
     run(git().args(["config", "user.name", "Cargo Public"]));
 
     // Now go through all directories and create git commits and tags from them
-    for version in ["v0.1.0", "v0.1.1", "v0.2.0", "v0.3.0"] {
-        let copy_to_dest = |name| {
+    for version in versions {
+        let copy_to_dest = |file| {
             let mut from = PathBuf::from(test_apis_dir.as_ref());
-            from.push(&format!("example_api-{version}"));
-            from.push(name);
+            from.push(format!("{name}-{version}"));
+            from.push(file);
 
             let mut to = PathBuf::from(dest_dir.as_ref());
-            to.push(name);
+            to.push(file);
 
             fs::copy(from, to).unwrap();
         };
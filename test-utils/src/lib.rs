@@ -8,7 +8,8 @@ use std::path::PathBuf;
 use rustdoc_json::BuildOptions;
 
 mod create_test_git_repo;
-pub use create_test_git_repo::create_test_git_repo;
+#[allow(unused_imports)] // Not all test binaries that include this module use it
+pub use create_test_git_repo::{create_test_git_repo, create_test_git_repo_for_versions_of};
 
 /// Helper to get the path to a freshly built rustdoc JSON file for the given
 /// test-crate.
@@ -20,7 +21,7 @@ pub fn rustdoc_json_path_for_crate(test_crate: &str) -> PathBuf {
     rustdoc_json::build(
         BuildOptions::default()
             .toolchain("+nightly".to_owned())
-            .manifest_path(&format!("{}/Cargo.toml", test_crate))
+            .manifest_path(format!("{test_crate}/Cargo.toml"))
             .quiet(true),
     )
     .unwrap()